@@ -0,0 +1,58 @@
+//! Generates the module list that compiles every solution in `src/bin` into the main binary.
+//! Only used with the `in-process` feature, so a day that does not compile can not break the other commands.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    if env::var_os("CARGO_FEATURE_IN_PROCESS").is_none() {
+        return;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    // NOTE: solutions are named `<year>_<day>.rs`, e.g. `2023_01.rs`.
+                    let is_day = path.extension()? == "rs"
+                        && stem.len() == 7
+                        && stem.char_indices().all(|(i, c)| {
+                            if i == 4 {
+                                c == '_'
+                            } else {
+                                c.is_ascii_digit()
+                            }
+                        });
+                    is_day.then_some(stem)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut modules = String::new();
+    let mut solutions = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        modules.push_str(&format!(
+            "#[allow(dead_code, unused_imports)]\n#[path = {:?}]\nmod day_{day};\n\n",
+            path.display().to_string()
+        ));
+        solutions.push_str(&format!("        day_{day}::SOLUTION,\n"));
+    }
+
+    let generated = format!(
+        "{modules}/// All solutions in `src/bin`.\npub fn solutions() -> Vec<advent_of_code::template::registry::Solution> {{\n    vec![\n{solutions}    ]\n}}\n"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), generated).unwrap();
+}
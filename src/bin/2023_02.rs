@@ -1,8 +1,9 @@
+use advent_of_code::parse::{parse_all, uint, ParseError};
 use chumsky::{
     prelude::*,
     text::{keyword, newline},
 };
-use tailsome::IntoOption;
+use tailsome::{IntoOption, IntoResult};
 
 advent_of_code::solution!(2);
 
@@ -27,19 +28,16 @@ struct Draw {
 }
 
 fn game_parser() -> impl Parser<char, Vec<Game>, Error = Simple<char>> {
-    let id = keyword("Game")
-        .padded()
-        .ignore_then(text::int::<_, Simple<_>>(10).map(|s: String| s.parse::<u32>().unwrap()));
+    let id = keyword("Game").padded().ignore_then(uint());
 
-    let draws = text::int::<char, Simple<_>>(10)
-        .map(|s: String| s.parse::<u32>().unwrap())
+    let draws = uint::<u32>()
         .padded()
         .then(text::ident())
-        .map(|(count, name)| match name.as_str() {
-            "blue" => Colors::Blue(count),
-            "green" => Colors::Green(count),
-            "red" => Colors::Red(count),
-            _ => panic!("Unknown color {}", name),
+        .try_map(|(count, name), span| match name.as_str() {
+            "blue" => Ok(Colors::Blue(count)),
+            "green" => Ok(Colors::Green(count)),
+            "red" => Ok(Colors::Red(count)),
+            _ => Err(Simple::custom(span, format!("unknown color `{name}`"))),
         })
         .separated_by(just(',').padded())
         .map(|draw| {
@@ -57,8 +55,8 @@ fn game_parser() -> impl Parser<char, Vec<Game>, Error = Simple<char>> {
         .separated_by(newline())
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = game_parser().parse(input).unwrap();
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let games = parse_all(game_parser(), input)?;
 
     let bag = Draw {
         red: 12,
@@ -79,11 +77,11 @@ pub fn part_one(input: &str) -> Option<u32> {
             }
         })
         .sum::<u32>()
-        .into_some()
+        .into_ok()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = game_parser().parse(input).unwrap();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let games = parse_all(game_parser(), input)?;
 
     games
         .iter()
@@ -96,22 +94,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             max.red * max.green * max.blue
         })
         .sum::<u32>()
-        .into_some()
+        .into_ok()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 8.into_some());
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 2286.into_some());
-    }
-}
+advent_of_code::example_tests!();
@@ -1,11 +1,12 @@
 use std::{
     collections::HashSet,
     fmt::{Debug, Write},
-    iter::repeat,
+    iter::repeat_n,
     ops::Range,
     str::FromStr,
 };
 
+use advent_of_code::parse::{parse_all, ParseError};
 use chumsky::{prelude::*, text::newline};
 use slotmap::{new_key_type, SlotMap};
 use tailsome::IntoOption;
@@ -137,8 +138,7 @@ impl From<Vec<Vec<ParserCell>>> for Grid {
                     ParserCell::Symbol(symbol) => line.cells.push(Cell::Symbol(symbol)),
                     ParserCell::Number(((start, end), value)) => {
                         let key = numbers.insert(value);
-                        line.cells
-                            .extend(repeat(Cell::Number(key)).take(end - start));
+                        line.cells.extend(repeat_n(Cell::Number(key), end - start));
                     }
                 }
             }
@@ -151,10 +151,10 @@ impl From<Vec<Vec<ParserCell>>> for Grid {
 }
 
 impl FromStr for Grid {
-    type Err = Vec<Simple<char>>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        just('.')
+        let grid = just('.')
             .to(ParserCell::Empty)
             .or(
                 text::int::<char, Simple<_>>(10).map_with_span(|c: String, span: Range<usize>| {
@@ -166,8 +166,9 @@ impl FromStr for Grid {
             .or(newline().not().map(|c: char| ParserCell::Symbol(c)))
             .repeated()
             .separated_by(newline())
-            .map(Grid::from)
-            .parse(s)
+            .map(Grid::from);
+
+        parse_all(grid, s)
     }
 }
 
@@ -232,21 +233,4 @@ pub fn part_two(input: &str) -> Option<u32> {
     gear_ratios.into_some()
 }
 
-#[cfg(test)]
-mod tests {
-    use tailsome::IntoOption;
-
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 4361.into_some());
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 467835.into_some());
-    }
-}
+advent_of_code::example_tests!();
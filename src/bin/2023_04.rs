@@ -4,6 +4,7 @@ use std::{
     str::FromStr,
 };
 
+use advent_of_code::parse::{parse_all, uint, ParseError};
 use chumsky::{prelude::*, text::newline};
 use tailsome::{IntoOption, IntoResult};
 
@@ -30,15 +31,14 @@ impl Card {
     pub fn parser() -> impl Parser<char, (Id, Card), Error = Simple<char>> {
         let id = just("Card")
             .padded()
-            .ignore_then(text::int(10).map(|s: String| Id(s.parse::<usize>().unwrap())))
+            .ignore_then(uint().map(Id))
             .then_ignore(just(':').padded());
 
-        let cards = text::int(10)
-            .map(|s: String| s.parse::<u32>().unwrap())
+        let cards = uint::<u32>()
             .separated_by(just(' ').repeated())
             .collect::<HashSet<_>>();
 
-        id.then(cards.then_ignore(just('|').padded()))
+        id.then(cards.clone().then_ignore(just('|').padded()))
             .then(cards)
             .map(|((id, winning), numbers)| (id, Card { winning, numbers }))
     }
@@ -63,13 +63,11 @@ impl Deref for Cards {
 }
 
 impl FromStr for Cards {
-    type Err = Vec<Simple<char>>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self(
-            Card::parser()
-                .separated_by(newline())
-                .parse(s)?
+            parse_all(Card::parser().separated_by(newline()), s)?
                 .into_iter()
                 .collect::<BTreeMap<Id, Card>>(),
         )
@@ -81,8 +79,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     let cards = Cards::from_str(input).unwrap();
 
     cards
-        .iter()
-        .map(|(_, card)| match card.winning() {
+        .values()
+        .map(|card| match card.winning() {
             0 => 0,
             number => 2u32.pow(number as u32 - 1),
         })
@@ -93,8 +91,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let cards = Cards::from_str(input).unwrap();
     let mut counts = cards
-        .iter()
-        .map(|(id, _)| (*id, 1))
+        .keys()
+        .map(|id| (*id, 1))
         .collect::<HashMap<Id, u32>>();
 
     for (id, card) in (*cards).iter() {
@@ -107,21 +105,4 @@ pub fn part_two(input: &str) -> Option<u32> {
     counts.values().sum::<u32>().into_some()
 }
 
-#[cfg(test)]
-mod tests {
-    use tailsome::IntoOption;
-
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 13.into_some());
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 30.into_some());
-    }
-}
+advent_of_code::example_tests!();
@@ -0,0 +1,123 @@
+use std::{collections::HashMap, str::FromStr};
+
+use advent_of_code::{
+    interval::{Interval, IntervalMap, IntervalSet},
+    parse::{header, numbers, parse_all, uint, ParseError},
+};
+use chumsky::{prelude::*, text::newline};
+use itertools::Itertools;
+
+advent_of_code::solution!(5);
+
+#[derive(Debug)]
+struct Mapping {
+    seeds: Vec<u64>,
+    soil: IntervalMap<u64>,
+    fertilizer: IntervalMap<u64>,
+    water: IntervalMap<u64>,
+    light: IntervalMap<u64>,
+    temperature: IntervalMap<u64>,
+    humidity: IntervalMap<u64>,
+    location: IntervalMap<u64>,
+}
+
+impl Mapping {
+    fn maps(&self) -> [&IntervalMap<u64>; 7] {
+        [
+            &self.soil,
+            &self.fertilizer,
+            &self.water,
+            &self.light,
+            &self.temperature,
+            &self.humidity,
+            &self.location,
+        ]
+    }
+
+    pub fn location_from_seed(&self, seed: u64) -> u64 {
+        self.maps().iter().fold(seed, |value, map| map.get(value))
+    }
+
+    pub fn locations_from_seeds(&self, seeds: &IntervalSet<u64>) -> IntervalSet<u64> {
+        self.maps()
+            .iter()
+            .fold(seeds.clone(), |values, map| map.map_set(&values))
+    }
+
+    pub fn locations(&self) -> Vec<u64> {
+        self.seeds
+            .iter()
+            .map(|seed| self.location_from_seed(*seed))
+            .collect::<Vec<_>>()
+    }
+
+    pub fn min_locations(&self) -> Option<u64> {
+        let seeds = self
+            .seeds
+            .iter()
+            .tuples()
+            .map(|(start, len)| Interval::new(*start, start + len))
+            .collect::<IntervalSet<_>>();
+
+        self.locations_from_seeds(&seeds).min()
+    }
+}
+
+impl FromStr for Mapping {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let seeds = header("seeds", numbers());
+
+        let ranges = uint::<u64>().separated_by(just(' ')).exactly(3);
+
+        let mapping = text::ident()
+            .then(just("-to-"))
+            .ignore_then(text::ident())
+            .then_ignore(just(" map:").then(newline()))
+            .then(ranges.separated_by(newline()));
+
+        let almanac = seeds
+            .then_ignore(newline().repeated())
+            .then(
+                mapping
+                    .then_ignore(newline().repeated())
+                    .repeated()
+                    .exactly(7)
+                    .collect::<HashMap<_, _>>(),
+            )
+            .map(|(seeds, mappings)| {
+                let get_mappings = |name: &str| {
+                    mappings[name]
+                        .iter()
+                        .map(|range| (Interval::new(range[1], range[1] + range[2]), range[0]))
+                        .collect::<IntervalMap<u64>>()
+                };
+                Mapping {
+                    seeds,
+                    soil: get_mappings("soil"),
+                    fertilizer: get_mappings("fertilizer"),
+                    water: get_mappings("water"),
+                    light: get_mappings("light"),
+                    temperature: get_mappings("temperature"),
+                    humidity: get_mappings("humidity"),
+                    location: get_mappings("location"),
+                }
+            });
+
+        parse_all(almanac, s)
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Mapping::from_str(input)
+        .unwrap()
+        .locations()
+        .into_iter()
+        .min()
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    Mapping::from_str(input).unwrap().min_locations()
+}
+
+advent_of_code::example_tests!();
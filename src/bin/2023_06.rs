@@ -1,5 +1,6 @@
 use std::{ops::Deref, str::FromStr};
 
+use advent_of_code::parse::{header, numbers, parse_all, ParseError};
 use chumsky::{prelude::*, text::newline};
 use tailsome::IntoOption;
 
@@ -11,16 +12,8 @@ struct Race {
     distance: u64,
 }
 
-trait AssertPart {
-    const VALID: ();
-}
-
 struct Races<const P: usize>(Vec<Race>);
 
-impl<const P: usize> AssertPart for Races<P> {
-    const VALID: () = assert!(P >= 1 && P <= 2);
-}
-
 impl<const P: usize> Deref for Races<P> {
     type Target = Vec<Race>;
 
@@ -38,11 +31,7 @@ impl<const P: usize> From<Vec<Race>> for Races<P> {
 impl<const P: usize> Races<P> {
     fn numbers_parser() -> Box<dyn Parser<char, Vec<u64>, Error = Simple<char>>> {
         match P {
-            1 => Box::new(
-                text::int(10)
-                    .map(|s: String| s.parse::<u64>().unwrap())
-                    .separated_by(just(' ').repeated()),
-            ),
+            1 => Box::new(numbers()),
             2 => Box::new(text::int(10).separated_by(just(' ').repeated()).map(
                 |s: Vec<String>| {
                     vec![s
@@ -59,15 +48,12 @@ impl<const P: usize> Races<P> {
 }
 
 impl<const P: usize> FromStr for Races<P> {
-    type Err = Vec<Simple<char>>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        just("Time:")
-            .padded()
-            .ignore_then(Self::numbers_parser())
+        let races = header("Time", Self::numbers_parser())
             .then_ignore(newline())
-            .then_ignore(just("Distance:").padded())
-            .then(Self::numbers_parser())
+            .then(header("Distance", Self::numbers_parser()))
             .map(|(times, distances)| {
                 times
                     .into_iter()
@@ -75,8 +61,9 @@ impl<const P: usize> FromStr for Races<P> {
                     .map(|(time, distance)| Race { time, distance })
                     .collect::<Vec<_>>()
                     .into()
-            })
-            .parse(s)
+            });
+
+        parse_all(races, s)
     }
 }
 
@@ -109,19 +96,4 @@ pub fn part_two(input: &str) -> Option<u64> {
         .into_some()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 288.into_some());
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 71503.into_some());
-    }
-}
+advent_of_code::example_tests!();
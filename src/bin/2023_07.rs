@@ -1,7 +1,8 @@
 use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 
+use advent_of_code::parse::{parse_all, uint, ParseError};
 use chumsky::{prelude::*, text::newline};
-use tailsome::{IntoOption, IntoResult};
+use tailsome::IntoResult;
 
 advent_of_code::solution!(7);
 
@@ -36,7 +37,7 @@ impl<const P: u8> Card<P> {
 
 impl<const P: u8> PartialOrd for Card<P> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.rank().cmp(&other.rank()))
+        Some(self.cmp(other))
     }
 }
 
@@ -177,56 +178,46 @@ impl<const P: u8> AsMut<Vec<Hand<P>>> for Game<P> {
 }
 
 impl<const P: u8> FromStr for Game<P> {
-    type Err = Vec<Simple<char>>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        filter_map(|_, c: char| Card::try_from(c).unwrap().into_ok())
+        let card = filter_map(|span, c: char| {
+            Card::try_from(c).map_err(|_| Simple::custom(span, format!("invalid card `{c}`")))
+        });
+
+        let hands = card
             .repeated()
             .exactly(5)
             .then_ignore(just(' '))
-            .then(text::int(10).map(|s: String| s.parse::<u64>().unwrap()))
+            .then(uint())
             .map(|(cards, bid)| (cards.as_slice().try_into().unwrap(), bid))
             .separated_by(newline())
-            .map(Game::from)
-            .parse(s)
+            .map(Game::from);
+
+        parse_all(hands, s)
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut game = Game::<1>::from_str(input).unwrap();
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let mut game = Game::<1>::from_str(input)?;
     game.as_mut().sort();
     game.as_mut()
         .iter()
         .enumerate()
         .map(|(index, hand)| hand.bid * (index as u64 + 1))
         .sum::<u64>()
-        .into_some()
+        .into_ok()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut game = Game::<2>::from_str(input).unwrap();
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let mut game = Game::<2>::from_str(input)?;
     game.as_mut().sort();
     game.as_mut()
         .iter()
         .enumerate()
         .map(|(index, hand)| hand.bid * (index as u64 + 1))
         .sum::<u64>()
-        .into_some()
+        .into_ok()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 6440.into_some());
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 5905.into_some());
-    }
-}
+advent_of_code::example_tests!();
@@ -1,6 +1,8 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, iter::successors, str::FromStr};
 
+use advent_of_code::{parse, search};
 use chumsky::{prelude::*, text::newline};
+use itertools::Itertools;
 use num::integer::lcm;
 use tailsome::{IntoOption, IntoResult};
 
@@ -47,50 +49,32 @@ struct Map {
 }
 
 impl FromStr for Map {
-    type Err = Vec<Simple<char>>;
+    type Err = parse::ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let directions = filter_map(|span, c: char| {
             Direction::try_from(c)
-                .map_err(|_| Simple::custom(span, format!("invalid direction {}", c)))
+                .map_err(|_| Simple::custom(span, format!("invalid direction `{c}`")))
         })
         .repeated();
 
-        let id = filter(|c: &char| c.is_ascii_alphanumeric())
-            .repeated()
-            .exactly(3)
-            .map(|c| c.into_iter().collect::<String>());
-
-        let node = id.then_ignore(just('=').padded()).then(
-            id.separated_by(just(',').padded())
-                .exactly(2)
-                .delimited_by(just('('), just(')')),
-        );
-
-        directions
+        let map = directions
             .then_ignore(newline().repeated())
-            .then(node.separated_by(newline()))
+            .then(parse::lines(parse::node()))
             .map(|(directions, nodes)| Map {
                 directions,
                 map: nodes
                     .into_iter()
-                    .map(|(k, mut v)| {
-                        (
-                            k,
-                            Node {
-                                right: v.pop().unwrap(),
-                                left: v.pop().unwrap(),
-                            },
-                        )
-                    })
+                    .map(|(k, (left, right))| (k, Node { left, right }))
                     .collect(),
-            })
-            .parse(s)
+            });
+
+        parse::parse_all(map, s)
     }
 }
 
 impl Map {
-    fn follow<'a>(&'a self, phase: usize, from: &'a str) -> Vec<&str> {
+    fn follow<'a>(&'a self, phase: usize, from: &'a str) -> Vec<&'a str> {
         let mut trail = Vec::<&str>::new();
 
         let mut current = from;
@@ -104,33 +88,43 @@ impl Map {
         unreachable!()
     }
 
-    fn period(&self, from: &str) -> usize {
-        let start = self.follow(0, from);
-        let repeat = self.follow(start.len(), start.last().unwrap());
+    fn step<'a>(&'a self, (node, phase): &(&'a str, usize)) -> (&'a str, usize) {
+        (
+            self.map[*node].take(self.directions[*phase]),
+            (phase + 1) % self.directions.len(),
+        )
+    }
+
+    /// Returns the number of steps between two visits of an end node by a ghost starting at `from`.
+    /// `None` if the ghost never reaches an end node, reaches them at uneven intervals, or does not reach
+    /// its first end node after exactly one period, as the lcm of all periods would be wrong in those cases.
+    fn period(&self, from: &str) -> Option<usize> {
+        let cycle = search::brent((from, 0), |state| self.step(state));
 
-        if start.len() != repeat.len() {
-            panic!("the first loop always has the same length as the final loop");
-        }
-        repeat.len()
+        // ghosts pass their end nodes at a fixed rate, which may be several times per cycle.
+        let ends: Vec<usize> = successors(Some((from, 0)), |state| self.step(state).into_some())
+            .skip(cycle.offset)
+            .take(cycle.period)
+            .positions(|(node, _)| node.ends_with('Z'))
+            .collect();
+
+        let (first, last) = (*ends.first()?, *ends.last()?);
+        let period = first + cycle.period - last;
+
+        let is_evenly_spaced = ends.windows(2).all(|pair| pair[1] - pair[0] == period);
+        let is_aligned = (cycle.offset + first) % period == 0;
+
+        (is_evenly_spaced && is_aligned).then_some(period)
     }
 
-    fn follow_ghost(&self) -> u64 {
-        let starts = self
-            .map
+    fn follow_ghost(&self) -> Option<u64> {
+        self.map
             .keys()
             .filter(|k| k.ends_with('A'))
-            .collect::<Vec<_>>();
-
-        let periods = starts
+            .map(|start| self.period(start).map(|period| period as u64))
+            .collect::<Option<Vec<_>>>()?
             .into_iter()
-            .map(|s| self.period(s))
-            .collect::<Vec<_>>();
-
-        periods
-            .iter()
-            .map(|period| *period as u64)
             .reduce(lcm)
-            .unwrap()
     }
 }
 
@@ -143,26 +137,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    Map::from_str(input).unwrap().follow_ghost().into_some()
+    Map::from_str(input).unwrap().follow_ghost()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, 6.into_some());
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, 6.into_some());
-    }
-}
+advent_of_code::example_tests!();
@@ -1,10 +1,11 @@
 use std::{iter::repeat, ops::Deref, str::FromStr};
 
-use chumsky::{prelude::*, text::newline};
+use advent_of_code::parse::{lines, numbers, parse_all, ParseError};
+use chumsky::prelude::*;
 use itertools::{FoldWhile, Itertools};
 use tailsome::IntoOption;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, parse = parse);
 
 #[derive(Debug)]
 struct History(Vec<i32>);
@@ -81,7 +82,7 @@ impl Derivatives {
 }
 
 #[derive(Debug)]
-struct Histories(Vec<Derivatives>);
+pub struct Histories(Vec<Derivatives>);
 
 impl Histories {
     fn iter(&self) -> impl Iterator<Item = &Derivatives> {
@@ -90,53 +91,25 @@ impl Histories {
 }
 
 impl FromStr for Histories {
-    type Err = Vec<Simple<char>>;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let history = just('-')
-            .or_not()
-            .then(text::int(10))
-            .map(|(sign, s)| s.parse::<i32>().unwrap() * if sign.is_some() { -1 } else { 1 })
-            .separated_by(just(' '))
-            .at_least(1)
-            .map(History)
-            .map(Derivatives::from);
-
-        history.separated_by(newline()).map(Histories).parse(s)
+        let history = numbers().map(History).map(Derivatives::from);
+
+        parse_all(lines(history).map(Histories), s)
     }
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    Histories::from_str(input)
-        .unwrap()
-        .iter()
-        .map(|h| h.next())
-        .sum::<i32>()
-        .into_some()
+fn parse(input: &str) -> Histories {
+    Histories::from_str(input).unwrap()
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    Histories::from_str(input)
-        .unwrap()
-        .iter()
-        .map(|h| h.prev())
-        .sum::<i32>()
-        .into_some()
+pub fn part_one(histories: &Histories) -> Option<i32> {
+    histories.iter().map(|h| h.next()).sum::<i32>().into_some()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 114.into_some());
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, 2.into_some());
-    }
+pub fn part_two(histories: &Histories) -> Option<i32> {
+    histories.iter().map(|h| h.prev()).sum::<i32>().into_some()
 }
+
+advent_of_code::example_tests!();
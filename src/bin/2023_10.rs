@@ -0,0 +1,194 @@
+use std::{fmt::Display, str::FromStr};
+
+use advent_of_code::{
+    geom::{self, Direction, Point},
+    grid::{Coord, Grid, GridError},
+};
+use itertools::Itertools;
+use tailsome::{IntoOption, IntoResult};
+
+advent_of_code::solution!(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pipe([Direction; 2]);
+
+impl TryFrom<char> for Pipe {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Pipe([Direction::North, Direction::East]),
+            'F' => Pipe([Direction::South, Direction::East]),
+            'J' => Pipe([Direction::North, Direction::West]),
+            '7' => Pipe([Direction::South, Direction::West]),
+            '|' => Pipe([Direction::North, Direction::South]),
+            '-' => Pipe([Direction::East, Direction::West]),
+            _ => ().into_err()?,
+        }
+        .into_ok()
+    }
+}
+
+impl Pipe {
+    fn connects(&self, direction: Direction) -> bool {
+        self.0.contains(&direction)
+    }
+
+    fn directions(&self) -> &[Direction; 2] {
+        &self.0
+    }
+}
+
+impl Display for Pipe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [north, east, south, west] = Direction::ALL.map(|d| self.connects(d));
+        f.write_str(match (north, east, south, west) {
+            (true, true, _, _) => "└",
+            (_, true, true, _) => "┌",
+            (_, _, true, true) => "┐",
+            (true, _, _, true) => "┘",
+            (true, _, true, _) => "│",
+            _ => "─",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Tile {
+    Ground,
+    Start,
+    Pipe(Pipe),
+}
+
+impl From<char> for Tile {
+    fn from(value: char) -> Self {
+        if value == 'S' {
+            return Tile::Start;
+        }
+
+        Pipe::try_from(value)
+            .map(Tile::Pipe)
+            .unwrap_or(Tile::Ground)
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tile::Ground => f.write_str("."),
+            Tile::Start => f.write_str("S"),
+            Tile::Pipe(pipe) => pipe.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Network {
+    tiles: Grid<Tile>,
+    start: Point,
+}
+
+#[derive(Debug)]
+enum NetworkError {
+    Grid(GridError),
+    MissingStart,
+    /// No two pipes next to the start lead back to it.
+    NoLoop,
+}
+
+impl Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkError::Grid(e) => e.fmt(f),
+            NetworkError::MissingStart => f.write_str("no start tile `S` found."),
+            NetworkError::NoLoop => f.write_str("no loop passes through the start tile."),
+        }
+    }
+}
+
+impl From<GridError> for NetworkError {
+    fn from(value: GridError) -> Self {
+        NetworkError::Grid(value)
+    }
+}
+
+impl FromStr for Network {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiles = s.parse::<Grid<Tile>>()?;
+        let start = tiles
+            .position(|tile| matches!(tile, Tile::Start))
+            .ok_or(NetworkError::MissingStart)?;
+
+        let directions = Direction::ALL
+            .into_iter()
+            .filter(|d| {
+                let neighbour = Coord::try_from(Point::from(start) + *d)
+                    .ok()
+                    .and_then(|coord| tiles.get(coord));
+                matches!(neighbour, Some(Tile::Pipe(pipe)) if pipe.connects(d.rev()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut network = Network {
+            tiles,
+            start: start.into(),
+        };
+
+        // the start is the pipe which closes the loop. other pipes may point at it as well.
+        for (a, b) in directions.into_iter().tuple_combinations() {
+            network.tiles[start] = Tile::Pipe(Pipe([a, b]));
+            if network.trace_loop().is_some() {
+                return network.into_ok();
+            }
+        }
+
+        Err(NetworkError::NoLoop)
+    }
+}
+
+impl Network {
+    fn pipe(&self, point: Point) -> Option<&Pipe> {
+        match Coord::try_from(point)
+            .ok()
+            .and_then(|coord| self.tiles.get(coord))
+        {
+            Some(Tile::Pipe(pipe)) => Some(pipe),
+            _ => None,
+        }
+    }
+
+    /// Follows the pipes from the start, or returns `None` if they do not lead back to it.
+    fn trace_loop(&self) -> Option<Vec<Point>> {
+        let mut points = vec![self.start];
+
+        let mut direction = self.pipe(self.start)?.directions()[0];
+        let mut current = self.start + direction;
+
+        while current != self.start {
+            let pipe = self.pipe(current).filter(|p| p.connects(direction.rev()))?;
+            points.push(current);
+            direction = *pipe.directions().iter().find(|d| **d != direction.rev())?;
+            current += direction;
+        }
+
+        self.pipe(self.start)?
+            .connects(direction.rev())
+            .then_some(points)
+    }
+
+    fn get_loop(&self) -> Vec<Point> {
+        self.trace_loop().unwrap()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    (Network::from_str(input).unwrap().get_loop().len() / 2).into_some()
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    geom::interior_points(&Network::from_str(input).unwrap().get_loop()).into_some()
+}
+
+advent_of_code::example_tests!();
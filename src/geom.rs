@@ -0,0 +1,445 @@
+/// Directions, points and vectors on the integer plane, plus helpers for lattice polygons.
+/// The y axis points down, like the rows of a puzzle input, so [`Direction::North`] decreases `y`.
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::grid::Coord;
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Rotates the direction by 90 degrees clockwise.
+    pub fn cw(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// Rotates the direction by 90 degrees counter-clockwise.
+    pub fn ccw(self) -> Self {
+        self.cw().rev()
+    }
+
+    /// Returns the opposite direction.
+    pub fn rev(self) -> Self {
+        self.cw().cw()
+    }
+
+    /// Returns the unit vector of the direction.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::South => Vector::new(0, 1),
+            Direction::West => Vector::new(-1, 0),
+        }
+    }
+}
+
+/// One of the four orthogonal and four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Rotates the direction by 45 degrees clockwise.
+    pub fn cw(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Rotates the direction by 45 degrees counter-clockwise.
+    pub fn ccw(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Returns the opposite direction.
+    pub fn rev(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Returns the vector of the direction, with a length of one on each axis.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction8::North => Vector::new(0, -1),
+            Direction8::NorthEast => Vector::new(1, -1),
+            Direction8::East => Vector::new(1, 0),
+            Direction8::SouthEast => Vector::new(1, 1),
+            Direction8::South => Vector::new(0, 1),
+            Direction8::SouthWest => Vector::new(-1, 1),
+            Direction8::West => Vector::new(-1, 0),
+            Direction8::NorthWest => Vector::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        Self::ALL[value as usize * 2]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A point on the integer plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the distance to `other` when moving orthogonally.
+    pub fn manhattan(self, other: Point) -> u64 {
+        (other - self).manhattan_len()
+    }
+
+    /// Returns the distance to `other` when moving orthogonally or diagonally.
+    pub fn chebyshev(self, other: Point) -> u64 {
+        (other - self).chebyshev_len()
+    }
+
+    /// Iterates the orthogonal neighbours, clockwise starting north.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d)
+    }
+
+    /// Iterates the orthogonal and diagonal neighbours, clockwise starting north.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<Coord> for Point {
+    #[allow(clippy::cast_possible_wrap)]
+    fn from(value: Coord) -> Self {
+        Self::new(value.x as i64, value.y as i64)
+    }
+}
+
+impl TryFrom<Point> for Coord {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(value: Point) -> Result<Self, Self::Error> {
+        Ok(Coord::new(value.x.try_into()?, value.y.try_into()?))
+    }
+}
+
+/// The difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Rotates the vector by 90 degrees clockwise.
+    pub fn rotate_cw(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates the vector by 90 degrees counter-clockwise.
+    pub fn rotate_ccw(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn manhattan_len(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    pub fn chebyshev_len(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Sub<Point> for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.vector()
+    }
+}
+
+impl Add<Direction8> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction8) -> Self::Output {
+        self + rhs.vector()
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Vector::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Vector::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns twice the area of a polygon, using the shoelace formula.
+/// The polygon is closed implicitly, i.e. the last vertex connects to the first one.
+/// Doubling keeps the result exact for vertices on the integer plane.
+pub fn double_area(vertices: &[Point]) -> u64 {
+    let Some(last) = vertices.last() else {
+        return 0;
+    };
+
+    vertices
+        .iter()
+        .scan(*last, |prev, p| {
+            let cross = prev.x * p.y - p.x * prev.y;
+            *prev = *p;
+            Some(cross)
+        })
+        .sum::<i64>()
+        .unsigned_abs()
+}
+
+/// Returns the number of integer points on the edges of a polygon.
+pub fn boundary_points(vertices: &[Point]) -> u64 {
+    let Some(last) = vertices.last() else {
+        return 0;
+    };
+
+    vertices
+        .iter()
+        .scan(*last, |prev, p| {
+            let Vector { x, y } = *p - *prev;
+            *prev = *p;
+            Some(gcd(x.unsigned_abs(), y.unsigned_abs()))
+        })
+        .sum()
+}
+
+/// Returns the number of integer points strictly inside a polygon, using Pick's theorem.
+///
+/// The vertices may be the corners of the polygon, or every point along its edges,
+/// e.g. a loop of tiles in a grid.
+pub fn interior_points(vertices: &[Point]) -> u64 {
+    if vertices.len() < 3 {
+        return 0;
+    }
+    // Pick's theorem: A = i + b/2 - 1
+    (double_area(vertices) + 2 - boundary_points(vertices)) / 2
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        boundary_points, double_area, interior_points, Direction, Direction8, Point, Vector,
+    };
+    use crate::grid::Coord;
+
+    #[test]
+    fn rotates_directions() {
+        assert_eq!(Direction::North.cw(), Direction::East);
+        assert_eq!(Direction::North.ccw(), Direction::West);
+        assert_eq!(Direction::East.rev(), Direction::West);
+        assert_eq!(Direction8::North.cw(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.ccw(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.rev(), Direction8::NorthEast);
+        assert_eq!(Direction8::from(Direction::South), Direction8::South);
+
+        for d in Direction::ALL {
+            assert_eq!(d.vector().rotate_cw(), d.cw().vector());
+            assert_eq!(d.vector().rotate_ccw(), d.ccw().vector());
+            assert_eq!(Direction8::from(d).vector(), d.vector());
+        }
+    }
+
+    #[test]
+    fn computes_diagonal_vectors() {
+        assert_eq!(Direction8::NorthEast.vector(), Vector::new(1, -1));
+        assert_eq!(Direction8::SouthEast.vector(), Vector::new(1, 1));
+        assert_eq!(Direction8::SouthWest.vector(), Vector::new(-1, 1));
+        assert_eq!(Direction8::NorthWest.vector(), Vector::new(-1, -1));
+    }
+
+    #[test]
+    fn computes_distances() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 4);
+        assert_eq!(a.manhattan(b), 6);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a + (b - a), b);
+        assert_eq!(
+            a + Vector::new(2, 0) * 2 - Vector::new(1, 1),
+            Point::new(4, 1)
+        );
+        assert_eq!(Point::ORIGIN + Direction::North, Point::new(0, -1));
+        assert_eq!(a.neighbours8().filter(|p| a.chebyshev(*p) == 1).count(), 8);
+    }
+
+    #[test]
+    fn converts_grid_coordinates() {
+        let point = Point::from(Coord::new(3, 4));
+        assert_eq!(point, Point::new(3, 4));
+        assert_eq!(Coord::try_from(point), Ok(Coord::new(3, 4)));
+        assert!(Coord::try_from(Point::new(-1, 0)).is_err());
+    }
+
+    #[test]
+    fn applies_picks_theorem() {
+        // a 4x3 rectangle, given by its corners
+        let corners = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 3),
+            Point::new(0, 3),
+        ];
+        assert_eq!(double_area(&corners), 24);
+        assert_eq!(boundary_points(&corners), 14);
+        assert_eq!(interior_points(&corners), 6);
+
+        // the same rectangle, given by every point along its edges
+        let mut path = vec![];
+        let mut p = Point::ORIGIN;
+        for (d, n) in [
+            (Direction::East, 4),
+            (Direction::South, 3),
+            (Direction::West, 4),
+            (Direction::North, 3),
+        ] {
+            for _ in 0..n {
+                path.push(p);
+                p += d;
+            }
+        }
+        assert_eq!(interior_points(&path), 6);
+    }
+}
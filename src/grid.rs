@@ -0,0 +1,417 @@
+/// A two-dimensional grid of cells, as found in many puzzle inputs.
+/// Cells are stored row by row and addressed by typed, non-negative coordinates.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// The coordinates of a cell in a [`Grid`]. `x` is the column, `y` is the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves the coordinates by an offset.
+    /// Returns [`None`] if the result would be negative.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Offsets of the orthogonal neighbours, clockwise starting north.
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the orthogonal and diagonal neighbours, clockwise starting north.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A row has a different length than the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character could not be converted to a cell.
+    InvalidCell { coord: Coord, value: char },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row."
+            ),
+            GridError::InvalidCell { coord, value } => {
+                write!(f, "invalid cell `{value}` at {coord}.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of cells.
+///
+/// Grids can be parsed from text, with one row per line, for any cell type that converts from [`char`]:
+///
+/// ```
+/// # use advent_of_code::grid::{Coord, Grid};
+/// let grid: Grid<char> = "#.\n.#".parse().unwrap();
+/// assert_eq!(grid[Coord::new(1, 1)], '#');
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    row: y,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses a grid from text with one row per line, converting each character with `f`.
+    /// Trailing empty lines are ignored.
+    pub fn parse_with<E>(
+        s: &str,
+        mut f: impl FnMut(Coord, char) -> Result<T, E>,
+    ) -> Result<Self, GridError> {
+        let rows = s
+            .trim_end_matches(['\n', '\r'])
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, value)| {
+                        let coord = Coord::new(x, y);
+                        f(coord, value).map_err(|_| GridError::InvalidCell { coord, value })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    /// Returns the cell at `coord`, or [`None`] if it is out of bounds.
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at `coord` mutably, or [`None`] if it is out of bounds.
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns the cell at `coord` moved by an offset, or [`None`] if it is out of bounds.
+    pub fn get_offset(&self, coord: Coord, dx: isize, dy: isize) -> Option<&T> {
+        coord.offset(dx, dy).and_then(|c| self.get(c))
+    }
+
+    /// Iterates all coordinates, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Coord::new(i % width, i / width))
+    }
+
+    /// Iterates all cells together with their coordinates, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    /// Iterates all cells mutably together with their coordinates, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.coords().zip(self.cells.iter_mut())
+    }
+
+    /// Returns the coordinates of the first cell that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Coord> {
+        self.iter()
+            .find(|(_, c)| predicate(c))
+            .map(|(coord, _)| coord)
+    }
+
+    /// Iterates the orthogonal neighbours of `coord` that are within bounds.
+    pub fn neighbours4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbours(coord, &OFFSETS_4)
+    }
+
+    /// Iterates the orthogonal and diagonal neighbours of `coord` that are within bounds.
+    pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbours(coord, &OFFSETS_8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        coord: Coord,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Coord> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |(dx, dy)| coord.offset(*dx, *dy))
+            .filter(|c| self.contains(*c))
+    }
+
+    /// Returns a row. Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // NOTE: `chunks` panics for a chunk size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates the cells of a column. Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Returns a grid with rows and columns swapped.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Returns a grid of the same size with `f` applied to each cell.
+    pub fn map<U>(&self, mut f: impl FnMut(Coord, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(coord, cell)| f(coord, cell)).collect(),
+        }
+    }
+
+    fn index_of(&self, coord: Coord) -> Option<usize> {
+        self.contains(coord)
+            .then_some(coord.y * self.width + coord.x)
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "coordinates {index} out of bounds for grid of size {}x{}",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("coordinates {index} out of bounds for grid of size {width}x{height}")
+        })
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |_, c| T::try_from(c))
+    }
+}
+
+/// Renders the grid with one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Coord, Grid, GridError};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(2, 1)], 'f');
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.get_offset(Coord::new(0, 0), -1, 0), None);
+        assert_eq!(grid.position(|c| *c == 'e'), Some(Coord::new(1, 1)));
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn parses_with_conversion() {
+        let grid = Grid::parse_with("12\n34", |_, c| c.to_digit(10).ok_or(())).unwrap();
+        assert_eq!(
+            grid.rows()
+                .map(|r| r.iter().sum::<u32>())
+                .collect::<Vec<_>>(),
+            [3, 7]
+        );
+
+        assert_eq!(
+            Grid::parse_with("12\n3x", |_, c| c.to_digit(10).ok_or(())),
+            Err(GridError::InvalidCell {
+                coord: Coord::new(1, 1),
+                value: 'x'
+            })
+        );
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = grid();
+        let corner = Coord::new(0, 0);
+        assert_eq!(
+            grid.neighbours4(corner).collect::<Vec<_>>(),
+            [Coord::new(1, 0), Coord::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours8(corner).count(), 3);
+        assert_eq!(grid.neighbours8(Coord::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+
+        let transposed = grid.transpose();
+        assert_eq!(transposed.to_string(), "ad\nbe\ncf");
+        assert_eq!(transposed.transpose(), grid);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_out_of_bounds_index() {
+        let _ = grid()[Coord::new(0, 2)];
+    }
+}
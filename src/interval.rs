@@ -0,0 +1,398 @@
+/// Half-open integer intervals, sets of intervals and piecewise maps that shift intervals by an offset.
+/// Mapping whole intervals instead of single values keeps puzzles with huge ranges cheap.
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, Range, Sub},
+};
+
+/// The integer types intervals can be made of.
+pub trait Bound: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Ord + Debug + Add<Output = T> + Sub<Output = T>> Bound for T {}
+
+/* -------------------------------------------------------------------------- */
+
+/// The half-open interval `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn len(&self) -> T {
+        self.end - self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Returns the values that are in both intervals, or [`None`] if there are none.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Splits the interval into the values before `at` and the values from `at` on.
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let at = at.clamp(self.start, self.end);
+        let before = Self::new(self.start, at);
+        let after = Self::new(at, self.end);
+        (
+            (!before.is_empty()).then_some(before),
+            (!after.is_empty()).then_some(after),
+        )
+    }
+
+    /// Moves the interval so it starts at `start`, keeping its length.
+    pub fn move_to(&self, start: T) -> Self {
+        Self::new(start, start + self.len())
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(value: Range<T>) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(value: Interval<T>) -> Self {
+        value.start..value.end
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of values, stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Iterates the intervals of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the smallest value of the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// Returns the smallest interval that contains all values of the set.
+    pub fn hull(&self) -> Option<Interval<T>> {
+        Some(Interval::new(
+            self.intervals.first()?.start,
+            self.intervals.last()?.end,
+        ))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    /// Adds an interval to the set, merging it with overlapping and adjacent intervals.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let mut merged = interval;
+        let mut rest = Vec::with_capacity(self.intervals.len() + 1);
+        let mut is_inserted = false;
+
+        for i in self.intervals.drain(..) {
+            if i.end < merged.start {
+                rest.push(i);
+            } else if merged.end < i.start {
+                if !is_inserted {
+                    rest.push(merged);
+                    is_inserted = true;
+                }
+                rest.push(i);
+            } else {
+                merged = Interval::new(merged.start.min(i.start), merged.end.max(i.end));
+            }
+        }
+
+        if !is_inserted {
+            rest.push(merged);
+        }
+
+        self.intervals = rest;
+    }
+
+    /// Returns the values that are in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for i in &other.intervals {
+            union.insert(*i);
+        }
+        union
+    }
+
+    /// Returns the values that are in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().filter_map(|b| a.intersect(b)))
+            .collect()
+    }
+
+    /// Returns the values that are in this set, but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = vec![];
+
+        for interval in &self.intervals {
+            let mut rest = Some(*interval);
+            for hole in &other.intervals {
+                let Some(current) = rest else {
+                    break;
+                };
+                let (before, _) = current.split_at(hole.start);
+                let (_, after) = current.split_at(hole.end);
+                difference.extend(before);
+                rest = after;
+            }
+            difference.extend(rest);
+        }
+
+        difference.into_iter().collect()
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals = iter
+            .into_iter()
+            .filter(|i| !i.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort_unstable();
+
+        let mut set = Self::new();
+        for i in intervals {
+            match set.intervals.last_mut() {
+                Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+                _ => set.intervals.push(i),
+            }
+        }
+        set
+    }
+}
+
+impl<T: Bound> From<Interval<T>> for IntervalSet<T> {
+    fn from(value: Interval<T>) -> Self {
+        Self::from_iter([value])
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise map of integers: each interval of the map shifts its values to a new start.
+/// Values outside of all intervals map to themselves.
+///
+/// ```
+/// # use advent_of_code::interval::{Interval, IntervalMap};
+/// let mut map = IntervalMap::new();
+/// map.insert(Interval::new(98, 100), 50);
+/// assert_eq!(map.get(99), 51);
+/// assert_eq!(map.get(10), 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalMap<T> {
+    /// The source intervals and the start they map to, sorted by source.
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: Bound> IntervalMap<T> {
+    pub fn new() -> Self {
+        Self { pieces: vec![] }
+    }
+
+    /// Maps the values of `source` to the values starting at `destination`.
+    /// Panics if `source` overlaps an interval that is already mapped.
+    pub fn insert(&mut self, source: Interval<T>, destination: T) {
+        if source.is_empty() {
+            return;
+        }
+
+        let index = self.pieces.partition_point(|(s, _)| s.start < source.start);
+        assert!(
+            self.pieces[index.saturating_sub(1)..]
+                .iter()
+                .take(2)
+                .all(|(s, _)| s.intersect(&source).is_none()),
+            "interval {source:?} overlaps an interval that is already mapped"
+        );
+        self.pieces.insert(index, (source, destination));
+    }
+
+    /// Maps a single value.
+    pub fn get(&self, value: T) -> T {
+        let index = self.pieces.partition_point(|(s, _)| s.start <= value);
+        match index.checked_sub(1).map(|i| self.pieces[i]) {
+            Some((source, destination)) if source.contains(value) => {
+                value - source.start + destination
+            }
+            _ => value,
+        }
+    }
+
+    /// Maps all values of an interval. The values may end up in several intervals.
+    pub fn map_interval(&self, interval: Interval<T>) -> IntervalSet<T> {
+        let mut mapped = vec![];
+        let mut rest = Some(interval);
+
+        for (source, destination) in &self.pieces {
+            let Some(current) = rest else {
+                break;
+            };
+
+            let (before, from_source) = current.split_at(source.start);
+            // values between the mapped intervals map to themselves.
+            mapped.extend(before);
+
+            let Some(from_source) = from_source else {
+                rest = None;
+                break;
+            };
+
+            let (inside, after) = from_source.split_at(source.end);
+            if let Some(inside) = inside {
+                mapped.push(inside.move_to(inside.start - source.start + *destination));
+            }
+            rest = after;
+        }
+
+        mapped.extend(rest);
+        mapped.into_iter().collect()
+    }
+
+    /// Maps all values of a set.
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.iter()
+            .flat_map(|i| self.map_interval(*i).intervals)
+            .collect()
+    }
+}
+
+impl<T: Bound> FromIterator<(Interval<T>, T)> for IntervalMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, destination) in iter {
+            map.insert(source, destination);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interval, IntervalMap, IntervalSet};
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|(s, e)| Interval::new(*s, *e))
+            .collect()
+    }
+
+    #[test]
+    fn intersects_and_splits_intervals() {
+        let a = Interval::new(0, 10);
+        assert_eq!(
+            a.intersect(&Interval::new(5, 15)),
+            Some(Interval::new(5, 10))
+        );
+        assert_eq!(a.intersect(&Interval::new(10, 15)), None);
+        assert_eq!(
+            a.split_at(3),
+            (Some(Interval::new(0, 3)), Some(Interval::new(3, 10)))
+        );
+        assert_eq!(a.split_at(-1), (None, Some(a)));
+        assert_eq!(a.split_at(10), (Some(a), None));
+        assert_eq!(Interval::from(2..4).move_to(10), Interval::new(10, 12));
+    }
+
+    #[test]
+    fn normalizes_sets() {
+        let s = set(&[(5, 8), (0, 2), (2, 3), (7, 10), (12, 12)]);
+        assert_eq!(
+            s.iter().copied().collect::<Vec<_>>(),
+            [Interval::new(0, 3), Interval::new(5, 10)]
+        );
+        assert_eq!(s.min(), Some(0));
+        assert_eq!(s.hull(), Some(Interval::new(0, 10)));
+        assert!(s.contains(9) && !s.contains(3));
+
+        let mut inserted = s.clone();
+        inserted.insert(Interval::new(3, 5));
+        assert_eq!(inserted, set(&[(0, 10)]));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12)]);
+        assert_eq!(a.union(&b), set(&[(0, 15)]));
+        assert_eq!(a.intersection(&b), set(&[(3, 5), (10, 12)]));
+        assert_eq!(a.difference(&b), set(&[(0, 3), (12, 15)]));
+        assert_eq!(b.difference(&a), set(&[(5, 10)]));
+    }
+
+    #[test]
+    fn maps_intervals() {
+        // the seed-to-soil map of 2023 day 5.
+        let map: IntervalMap<i64> = [(Interval::new(98, 100), 50), (Interval::new(50, 98), 52)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(99), 51);
+        assert_eq!(map.get(10), 10);
+
+        assert_eq!(
+            map.map_interval(Interval::new(45, 99)),
+            set(&[(45, 51), (52, 100)])
+        );
+        assert_eq!(map.map_set(&set(&[(97, 99)])), set(&[(50, 51), (99, 100)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_overlapping_pieces() {
+        let mut map = IntervalMap::new();
+        map.insert(Interval::new(0, 10), 20);
+        map.insert(Interval::new(5, 15), 40);
+    }
+}
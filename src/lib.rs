@@ -1,4 +1,8 @@
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub mod parse;
+pub mod search;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
-
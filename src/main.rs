@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, bench, download, read, readme, scaffold, solve, status, time, verify, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,7 +9,11 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::all::TimeoutOptions;
+    use advent_of_code::template::commands::scaffold::ScaffoldOptions;
+    use advent_of_code::template::commands::solve::SolveOptions;
+    use advent_of_code::template::commands::status::StatusOptions;
+    use advent_of_code::template::{Day, Year};
     use std::process;
 
     pub enum AppArguments {
@@ -19,43 +25,119 @@ mod args {
         },
         Scaffold {
             day: Day,
-            download: bool,
+            options: ScaffoldOptions,
         },
         Solve {
             day: Day,
-            release: bool,
-            time: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            options: SolveOptions,
         },
         All {
             release: bool,
             time: bool,
+            budget: Option<u64>,
+            isolated: bool,
+            timeouts: TimeoutOptions,
         },
         Time {
             all: bool,
             day: Option<Day>,
+            budget: Option<u64>,
+            isolated: bool,
+            compare: bool,
+            threshold: Option<f64>,
+            timeouts: TimeoutOptions,
+        },
+        Bench {
+            day: Option<Day>,
+            budget: Option<u64>,
+            timeouts: TimeoutOptions,
+        },
+        Verify {
+            day: Option<Day>,
+            record: bool,
+        },
+        Watch {
+            day: Day,
+            release: bool,
+        },
+        Status {
+            options: StatusOptions,
         },
+        Readme,
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parses the command and the year it applies to.
+    /// The year is read from the `--year` option, falling back to the `AOC_YEAR` environment variable.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // NOTE: parse `--year` before free arguments, so it is not mistaken for a day.
+        let year = match args.opt_value_from_str("--year")? {
+            Some(year) => year,
+            None => Year::from_env().ok_or(
+                "no year specified. Pass `--year <year>` or set the `AOC_YEAR` environment variable.",
+            )?,
+        };
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                budget: args.opt_value_from_str("--budget")?,
+                isolated: args.contains("--isolated"),
+                timeouts: parse_timeouts(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
+                let isolated = args.contains("--isolated");
+                let budget = args.opt_value_from_str("--budget")?;
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let timeouts = parse_timeouts(&mut args)?;
 
                 AppArguments::Time {
                     all,
+                    isolated,
+                    budget,
+                    compare,
+                    threshold,
+                    timeouts,
+                    day: args.opt_free_from_str()?,
+                }
+            }
+            Some("bench") => {
+                let budget = args.opt_value_from_str("--budget")?;
+                let timeouts = parse_timeouts(&mut args)?;
+
+                AppArguments::Bench {
+                    budget,
+                    timeouts,
                     day: args.opt_free_from_str()?,
                 }
             }
+            Some("verify") => {
+                let record = args.contains("--record");
+
+                AppArguments::Verify {
+                    record,
+                    day: args.opt_free_from_str()?,
+                }
+            }
+            Some("watch") => AppArguments::Watch {
+                release: args.contains("--release"),
+                day: args.free_from_str()?,
+            },
+            Some("status") => AppArguments::Status {
+                options: StatusOptions {
+                    json: args.contains("--json"),
+                    no_tests: args.contains("--no-tests"),
+                },
+            },
+            Some("readme") => AppArguments::Readme,
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -64,14 +146,23 @@ mod args {
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
-                download: args.contains("--download"),
+                options: ScaffoldOptions {
+                    template: args.opt_value_from_str("--template")?,
+                    download: args.contains("--download"),
+                    repair: args.contains("--repair"),
+                    force: args.contains("--force"),
+                },
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-                dhat: args.contains("--dhat"),
+                options: SolveOptions {
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    time: args.contains("--time"),
+                    budget: args.opt_value_from_str("--budget")?,
+                    dhat: args.contains("--dhat"),
+                    input: args.opt_value_from_str("--input")?,
+                },
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -90,41 +181,84 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
+    }
+
+    /// Parses the `--timeout <seconds>` and `--day-timeout <seconds>` options of multi-day runs.
+    fn parse_timeouts(args: &mut pico_args::Arguments) -> Result<TimeoutOptions, pico_args::Error> {
+        Ok(TimeoutOptions {
+            part: args.opt_value_from_str("--timeout")?,
+            day: args.opt_value_from_str("--day-timeout")?,
+        })
     }
 }
 
+// NOTE: the example tests of the days already run as part of their own bins.
+#[cfg(all(feature = "in-process", not(feature = "dhat-heap"), not(test)))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 fn main() {
+    // NOTE: the dhat allocator of the solutions would clash with each other, so they are not compiled in.
+    #[cfg(all(feature = "in-process", not(feature = "dhat-heap"), not(test)))]
+    advent_of_code::template::registry::register(solutions::solutions());
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Time { day, all } => time::handle(day, all),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
+        Ok((year, args)) => match args {
+            AppArguments::All {
                 release,
                 time,
-                dhat,
-                submit,
-            } => solve::handle(day, release, time, dhat, submit),
+                budget,
+                isolated,
+                timeouts,
+            } => all::handle(year, release, time, budget, isolated, &timeouts),
+            AppArguments::Time {
+                day,
+                all,
+                budget,
+                isolated,
+                compare,
+                threshold,
+                timeouts,
+            } => {
+                let compare = compare.then(|| time::CompareOptions {
+                    threshold: threshold.unwrap_or(time::DEFAULT_THRESHOLD),
+                });
+                time::handle(year, day, all, budget, isolated, compare, &timeouts);
+            }
+            AppArguments::Bench {
+                day,
+                budget,
+                timeouts,
+            } => bench::handle(year, day, budget, &timeouts),
+            AppArguments::Verify { day, record } => verify::handle(year, day, record),
+            AppArguments::Watch { day, release } => watch::handle(year, day, release),
+            AppArguments::Status { options } => status::handle(year, options),
+            AppArguments::Readme => readme::handle(year),
+            AppArguments::Download { day } => download::handle(year, day),
+            AppArguments::Read { day } => read::handle(year, day),
+            AppArguments::Scaffold { day, options } => scaffold::handle(year, day, options),
+            AppArguments::Solve { day, options } => solve::handle(year, day, options),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(
+                            year,
+                            day,
+                            scaffold::ScaffoldOptions {
+                                template: None,
+                                download: true,
+                                repair: false,
+                                force: false,
+                            },
+                        );
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,400 @@
+/// Reusable [`chumsky`] parsers for shapes that puzzle inputs commonly have.
+/// All parsers use [`Simple<char>`] errors and can be combined with any other chumsky parser.
+/// [`parse_all`] reports failures as a [`ParseError`], which shows where in the input parsing went wrong.
+use std::{
+    fmt::{self, Debug, Display},
+    str::FromStr,
+};
+
+use chumsky::{
+    error::SimpleReason,
+    prelude::*,
+    text::{newline, Character},
+};
+
+use crate::grid::Grid;
+
+/// The error of the parsers in this module.
+pub type Error = Simple<char>;
+
+/// An unsigned integer, converted to any type that implements [`FromStr`], e.g. `u32` or `usize`.
+/// Fails with a custom error if the number does not fit the type.
+pub fn uint<T>() -> impl Parser<char, T, Error = Error> + Clone
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text::int(10).try_map(|s: String, span| {
+        s.parse()
+            .map_err(|e| Simple::custom(span, format!("invalid number `{s}`: {e}")))
+    })
+}
+
+/// An integer with an optional leading `-`, converted to any type that implements [`FromStr`], e.g. `i64`.
+pub fn int<T>() -> impl Parser<char, T, Error = Error> + Clone
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    just('-').or_not().then(text::int(10)).try_map(
+        |(sign, digits): (Option<char>, String), span| {
+            let s = format!("{}{digits}", if sign.is_some() { "-" } else { "" });
+            s.parse()
+                .map_err(|e| Simple::custom(span, format!("invalid number `{s}`: {e}")))
+        },
+    )
+}
+
+/// Spaces and tabs, but no line breaks.
+pub fn inline_whitespace() -> impl Parser<char, (), Error = Error> + Clone {
+    filter(|c: &char| c.is_inline_whitespace())
+        .repeated()
+        .ignored()
+}
+
+/// At least one integer, separated by spaces on a single line, e.g. `7  15   30` or `0 -3 6`.
+pub fn numbers<T>() -> impl Parser<char, Vec<T>, Error = Error> + Clone
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    int()
+        .separated_by(
+            filter(|c: &char| c.is_inline_whitespace())
+                .repeated()
+                .at_least(1),
+        )
+        .at_least(1)
+}
+
+/// A line that starts with `key:`, followed by `values`, e.g. `Time:      7  15   30`.
+pub fn header<T>(
+    key: &str,
+    values: impl Parser<char, T, Error = Error>,
+) -> impl Parser<char, T, Error = Error> {
+    just(key.to_string())
+        .then(just(':'))
+        .then(inline_whitespace())
+        .ignore_then(values)
+}
+
+/// Items that are separated by line breaks.
+/// A trailing line break is left to the surrounding parser, e.g. [`parse_all`].
+pub fn lines<T>(
+    line: impl Parser<char, T, Error = Error>,
+) -> impl Parser<char, Vec<T>, Error = Error> {
+    line.separated_by(newline())
+}
+
+/// Blocks that are separated by blank lines, e.g. the sections of an almanac.
+pub fn blocks<T>(
+    block: impl Parser<char, T, Error = Error>,
+) -> impl Parser<char, Vec<T>, Error = Error> {
+    block.separated_by(newline().repeated().at_least(2))
+}
+
+/// A rectangular grid of characters, converted to cells with [`TryFrom<char>`], one row per line.
+pub fn grid<T: TryFrom<char>>() -> impl Parser<char, Grid<T>, Error = Error> + Clone {
+    let cell = filter(|c: &char| *c != '\n' && *c != '\r').try_map(|c, span| {
+        T::try_from(c).map_err(|_| Simple::custom(span, format!("invalid cell `{c}`")))
+    });
+
+    let row = cell.repeated().at_least(1);
+
+    row.separated_by(newline()).try_map(|rows, span| {
+        Grid::from_rows(rows).map_err(|e| Simple::custom(span, e.to_string()))
+    })
+}
+
+/// A name of letters and digits, e.g. `AAA` or `11Z`.
+pub fn name() -> impl Parser<char, String, Error = Error> + Clone {
+    filter(|c: &char| c.is_ascii_alphanumeric())
+        .repeated()
+        .at_least(1)
+        .collect()
+}
+
+/// A node with two neighbours, written as `X = (A, B)`.
+pub fn node() -> impl Parser<char, (String, (String, String)), Error = Error> + Clone {
+    name().then_ignore(just('=').padded()).then(
+        name()
+            .then_ignore(just(',').padded())
+            .then(name())
+            .delimited_by(just('('), just(')')),
+    )
+}
+
+/// Parses a whole input, allowing trailing whitespace.
+pub fn parse_all<T>(
+    parser: impl Parser<char, T, Error = Error>,
+    input: &str,
+) -> Result<T, ParseError> {
+    parser
+        .then_ignore(text::whitespace())
+        .then_ignore(end())
+        .parse(input)
+        .map_err(|errors| ParseError::new(input, errors))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The errors of a failed parse, together with the input they occurred in.
+/// Both [`Display`] and [`Debug`] render every error as an annotated snippet of its line:
+///
+/// ```text
+/// expected `:`, found `x` at line 2, column 9
+///   |
+/// 2 | Distancex  9  40
+///   |         ^
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    input: String,
+    errors: Vec<Error>,
+}
+
+impl ParseError {
+    pub fn new(input: &str, errors: Vec<Error>) -> Self {
+        Self {
+            input: input.to_string(),
+            errors,
+        }
+    }
+
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Returns a single line that describes the first error, e.g. `expected `:`, found `x` at line 2, column 9`.
+    pub fn reason(&self) -> String {
+        match self.errors.first() {
+            Some(error) => self.headline(error),
+            None => "invalid input".into(),
+        }
+    }
+
+    fn headline(&self, error: &Error) -> String {
+        let (line, column, _) = locate(&self.input, error.span().start);
+        format!("{} at line {line}, column {column}", describe(error))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.errors.is_empty() {
+            return write!(f, "{}", self.reason());
+        }
+
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let span = error.span();
+            let (line, column, text) = locate(&self.input, span.start);
+            let gutter = " ".repeat(line.to_string().len());
+
+            // keep tabs, so the caret lines up with the offending character.
+            let indent = text
+                .chars()
+                .take(column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect::<String>();
+            let width = span
+                .len()
+                .min(text.chars().count().saturating_sub(column - 1))
+                .max(1);
+
+            writeln!(f, "{}", self.headline(error))?;
+            writeln!(f, "{gutter} |")?;
+            writeln!(f, "{line} | {text}")?;
+            write!(f, "{gutter} | {indent}{}", "^".repeat(width))?;
+
+            if i + 1 < self.errors.len() {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for ParseError {
+    /// Shows the annotated snippets, so that unwrapping a failed parse points at the input.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Describes what went wrong, without the location.
+fn describe(error: &Error) -> String {
+    match error.reason() {
+        SimpleReason::Custom(message) => message.clone(),
+        SimpleReason::Unclosed { delimiter, .. } => {
+            format!("unclosed delimiter {}", token(Some(delimiter)))
+        }
+        SimpleReason::Unexpected => {
+            let found = token(error.found());
+            let expected = match error.label() {
+                Some(label) => vec![label.to_string()],
+                None => expected_tokens(error),
+            };
+
+            match expected.as_slice() {
+                [] => format!("unexpected {found}"),
+                [one] => format!("expected {one}, found {found}"),
+                [rest @ .., last] => {
+                    format!("expected {} or {last}, found {found}", rest.join(", "))
+                }
+            }
+        }
+    }
+}
+
+/// Lists the expected tokens of an error, collapsing a full set of digits into `digit`.
+fn expected_tokens(error: &Error) -> Vec<String> {
+    let mut expected = error.expected().copied().collect::<Vec<_>>();
+    let is_digit = |c: &Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+
+    let mut tokens = vec![];
+    if expected.iter().filter(|c| is_digit(c)).count() == 10 {
+        expected.retain(|c| !is_digit(c));
+        tokens.push("digit".to_string());
+    }
+
+    tokens.extend(expected.iter().map(|c| token(c.as_ref())));
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+fn token(c: Option<&char>) -> String {
+    match c {
+        None => "end of input".into(),
+        Some('\n') => "line break".into(),
+        Some(c) => format!("`{c}`"),
+    }
+}
+
+/// Returns the line and column (both starting at 1) of a char index, and the text of its line.
+fn locate(input: &str, index: usize) -> (usize, usize, &str) {
+    let mut line_start = 0;
+    let mut last = (1, 1, "");
+
+    for (i, raw) in input.split('\n').enumerate() {
+        let text = raw.trim_end_matches('\r');
+        let len = text.chars().count();
+        let raw_len = raw.chars().count();
+        if index <= line_start + raw_len {
+            return (i + 1, (index - line_start).min(len) + 1, text);
+        }
+        line_start += raw_len + 1;
+        last = (i + 1, len + 1, text);
+    }
+
+    last
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use chumsky::prelude::*;
+
+    use super::{blocks, grid, header, int, lines, node, numbers, parse_all, uint};
+    use crate::grid::{Coord, Grid};
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse_all(uint::<u32>(), "42"), Ok(42));
+        assert_eq!(parse_all(int::<i64>(), "-42"), Ok(-42));
+        assert!(parse_all(uint::<u8>(), "256").is_err());
+        assert!(parse_all(uint::<u32>(), "-1").is_err());
+
+        assert_eq!(
+            parse_all(numbers::<i32>(), "0  3\t-6 9\n"),
+            Ok(vec![0, 3, -6, 9])
+        );
+        assert_eq!(
+            parse_all(lines(numbers::<u8>()), "1 2\n3 4"),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+    }
+
+    #[test]
+    fn parses_headers() {
+        let races = header("Time", numbers::<u32>())
+            .then_ignore(text::newline())
+            .then(header("Distance", numbers::<u32>()));
+        assert_eq!(
+            parse_all(races, "Time:      7  15\nDistance:  9  40\n"),
+            Ok((vec![7, 15], vec![9, 40]))
+        );
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let input = "seeds: 1 2\n\na-to-b map:\n1 2 3\n4 5 6\n\nb-to-c map:\n7 8 9\n";
+        let block = lines(
+            numbers::<u32>().or(filter(|c: &char| *c != '\n')
+                .repeated()
+                .at_least(1)
+                .to(vec![])),
+        );
+        let blocks = parse_all(blocks(block), input).unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1], [vec![], vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn parses_grids_and_nodes() {
+        let parsed: Grid<char> = parse_all(grid(), "#.\n.#\n").unwrap();
+        assert_eq!(parsed[Coord::new(1, 1)], '#');
+        assert!(parse_all(grid::<char>(), "#.\n#\n").is_err());
+
+        assert_eq!(
+            parse_all(lines(node()), "AAA = (BBB, CCC)\n11Z = (11B, XXX)"),
+            Ok(vec![
+                ("AAA".into(), ("BBB".into(), "CCC".into())),
+                ("11Z".into(), ("11B".into(), "XXX".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        let races = header("Time", numbers::<u32>())
+            .then_ignore(text::newline())
+            .then(header("Distance", numbers::<u32>()));
+        let error = parse_all(races, "Time:      7  15\nDistancex  9  40\n").unwrap_err();
+
+        assert_eq!(
+            error.reason(),
+            "expected `:`, found `x` at line 2, column 9"
+        );
+        assert_eq!(
+            error.to_string(),
+            [
+                "expected `:`, found `x` at line 2, column 9",
+                "  |",
+                "2 | Distancex  9  40",
+                "  |         ^",
+            ]
+            .join("\n")
+        );
+
+        let error = parse_all(lines(numbers::<u8>()), "1 2\n3 x").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            "expected `-`, `0` or end of input, found `x` at line 2, column 3"
+        );
+
+        let error = parse_all(uint::<u8>(), "256").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            "invalid number `256`: number too large to fit in target type at line 1, column 1"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+/// Graph searches and cycle detection.
+/// Graphs are never built explicitly: every search takes a start node and a closure that returns the neighbours of a node,
+/// so nodes can be grid coordinates, points, `&str` keys of a node map or any other hashable value.
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/* -------------------------------------------------------------------------- */
+
+/// Visits all nodes reachable from `start` in breadth-first order.
+/// Returns the number of steps from `start` to each visited node.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Finds a path with the fewest steps from `start` to a node that satisfies `is_goal`.
+/// The path includes both `start` and the goal.
+pub fn bfs_path<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(unwind(node, |n| parents[n].clone()));
+        }
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Visits all nodes reachable from `start` in depth-first order and returns them in the order they were visited.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+
+        // NOTE: neighbours are pushed in reverse, so the first neighbour is visited first.
+        let mut next = neighbours(&node)
+            .into_iter()
+            .filter(|n| !visited.contains(n))
+            .collect::<Vec<_>>();
+        next.reverse();
+        stack.extend(next);
+
+        order.push(node);
+    }
+
+    order
+}
+
+/// Splits `nodes` into groups of nodes that are connected to each other.
+/// `neighbours` should be symmetric, i.e. if `b` is a neighbour of `a`, `a` is a neighbour of `b`.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+
+        let component = bfs(node, &mut neighbours).into_keys().collect::<Vec<_>>();
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds a cheapest path from `start` to a node that satisfies `is_goal`.
+/// `neighbours` returns each neighbour together with the cost of moving there, which must not be negative.
+/// Returns the path, including `start` and the goal, and its total cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Finds a cheapest path from `start` to a node that satisfies `is_goal`, guided by `heuristic`.
+/// The heuristic estimates the remaining cost to a goal and must never overestimate it,
+/// e.g. the Manhattan distance on a grid where each step costs at least one.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // NOTE: nodes are stored once and referenced by index, so they don't need to be `Ord`.
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    // the cheapest known cost and the parent of each node.
+    let mut best: Vec<(C, Option<usize>)> = vec![(C::default(), None)];
+
+    let mut heap = BinaryHeap::from([Candidate {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        index: 0,
+    }]);
+
+    while let Some(Candidate { cost, index, .. }) = heap.pop() {
+        if cost > best[index].0 {
+            continue;
+        }
+
+        let node = nodes[index].clone();
+        if is_goal(&node) {
+            let path = unwind(index, |i| best[*i].1)
+                .into_iter()
+                .map(|i| nodes[i].clone())
+                .collect();
+            return Some((path, cost));
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(entry) => {
+                    let i = *entry.get();
+                    if next_cost >= best[i].0 {
+                        continue;
+                    }
+                    best[i] = (next_cost, Some(index));
+                    i
+                }
+                Entry::Vacant(entry) => {
+                    nodes.push(entry.key().clone());
+                    best.push((next_cost, Some(index)));
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+
+            heap.push(Candidate {
+                estimate: next_cost + heuristic(&nodes[next_index]),
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+    }
+
+    None
+}
+
+/// A node in the queue of [`astar`], ordered so that the [`BinaryHeap`] pops the lowest estimate first.
+struct Candidate<C> {
+    estimate: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: Ord> Ord for Candidate<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<C: Ord> PartialOrd for Candidate<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> PartialEq for Candidate<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for Candidate<C> {}
+
+/// Follows parents from `end` back to the start and returns the path in forward order.
+fn unwind<N>(end: N, mut parent: impl FnMut(&N) -> Option<N>) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(prev) = parent(path.last().unwrap()) {
+        path.push(prev);
+    }
+    path.reverse();
+    path
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The cycle of a sequence `x, f(x), f(f(x)), ...`, as found by [`floyd`] or [`brent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The index of the first element that is part of the cycle.
+    pub offset: usize,
+    /// The length of the cycle.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Maps the index of an element to the index of the same element in the first pass of the cycle.
+    /// This allows finding e.g. the billionth element without computing it.
+    pub fn reduce(&self, index: usize) -> usize {
+        if index < self.offset {
+            index
+        } else {
+            self.offset + (index - self.offset) % self.period
+        }
+    }
+}
+
+/// Finds the cycle of the sequence `start, f(start), f(f(start)), ...` with Floyd's tortoise and hare.
+/// The sequence must eventually repeat, otherwise this never returns.
+pub fn floyd<T: PartialEq + Clone>(start: T, f: impl Fn(&T) -> T) -> Cycle {
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&f(&hare));
+    }
+
+    let mut offset = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        offset += 1;
+    }
+
+    let mut period = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+
+    Cycle { offset, period }
+}
+
+/// Finds the cycle of the sequence `start, f(start), f(f(start)), ...` with Brent's algorithm.
+/// Usually needs fewer calls of `f` than [`floyd`].
+/// The sequence must eventually repeat, otherwise this never returns.
+pub fn brent<T: PartialEq + Clone>(start: T, f: impl Fn(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        offset += 1;
+    }
+
+    Cycle { offset, period }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{astar, bfs, bfs_path, brent, connected_components, dfs, dijkstra, floyd, Cycle};
+    use crate::grid::{Coord, Grid};
+
+    fn maze() -> Grid<char> {
+        "S.#.\n.##.\n...E".parse().unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<char>, coord: Coord) -> Vec<Coord> {
+        grid.neighbours4(coord)
+            .filter(|c| grid[*c] != '#')
+            .collect()
+    }
+
+    #[test]
+    fn searches_grids_breadth_first() {
+        let grid = maze();
+        let start = grid.position(|c| *c == 'S').unwrap();
+        let end = grid.position(|c| *c == 'E').unwrap();
+
+        let distances = bfs(start, |c| open_neighbours(&grid, *c));
+        assert_eq!(distances[&end], 5);
+        assert_eq!(distances.len(), 9);
+
+        let path = bfs_path(start, |c| open_neighbours(&grid, *c), |c| *c == end).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+    }
+
+    #[test]
+    fn searches_node_maps_depth_first() {
+        let map = HashMap::from([
+            ("AAA", vec!["BBB", "CCC"]),
+            ("BBB", vec!["DDD"]),
+            ("CCC", vec![]),
+            ("DDD", vec!["AAA"]),
+        ]);
+
+        assert_eq!(dfs("AAA", |n| map[n].clone()), ["AAA", "BBB", "DDD", "CCC"]);
+        assert_eq!(bfs_path("CCC", |n| map[n].clone(), |n| *n == "AAA"), None);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // moving right is cheap, moving down is expensive.
+        let grid = Grid::new(4, 4, ());
+        let neighbours = |c: &Coord| {
+            grid.neighbours4(*c)
+                .map(|n| (n, if n.y > c.y { 5 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let end = Coord::new(3, 3);
+
+        let (path, cost) = dijkstra(Coord::new(0, 0), neighbours, |c| *c == end).unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 7);
+
+        let (_, cost) = astar(
+            Coord::new(0, 0),
+            neighbours,
+            |c| end.x - c.x + end.y - c.y,
+            |c| *c == end,
+        )
+        .unwrap();
+        assert_eq!(cost, 18);
+    }
+
+    #[test]
+    fn finds_connected_components() {
+        let grid: Grid<char> = "aab\nbab\nbbc".parse().unwrap();
+        let mut components = connected_components(grid.coords(), |c| {
+            grid.neighbours4(*c)
+                .filter(|n| grid[*n] == grid[*c])
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .map(|c| c.len())
+        .collect::<Vec<_>>();
+        components.sort_unstable();
+        assert_eq!(components, [1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        // 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+        let f = |x: &u32| if *x == 5 { 3 } else { x + 1 };
+        let expected = Cycle {
+            offset: 3,
+            period: 3,
+        };
+        assert_eq!(floyd(0, f), expected);
+        assert_eq!(brent(0, f), expected);
+        assert_eq!(expected.reduce(2), 2);
+        assert_eq!(expected.reduce(1_000_000_000), 4);
+    }
+}
@@ -0,0 +1,298 @@
+/// Module that stores the correct answers of solved days.
+/// Recorded answers are used to detect regressions when a solution is refactored.
+/// An answer accepted by advent of code, as kept in the submission ledger, wins over the one in `answers.json`.
+use std::{collections::HashMap, fs, io::Error, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::ledger::Ledger;
+use crate::template::{get_data_path, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the correct answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// A part whose answer in `answers.json` differs from the answer advent of code accepted.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub day: Day,
+    pub part: u8,
+    pub recorded: String,
+    pub accepted: String,
+}
+
+/// Represents the correct answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate the answers of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_data_path(year, ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the answers of a year from a JSON file. If not present, returns empty answers.
+    /// A file that can not be read is reported and treated as empty, so use [`Answers::try_read_from_file`]
+    /// before storing the answers, which would overwrite it.
+    pub fn read_from_file(year: Year) -> Self {
+        Self::try_read_from_file(year).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Answers::default()
+        })
+    }
+
+    /// Rehydrate the answers of a year from a JSON file. If not present, returns empty answers.
+    /// Fails if the file exists but can not be read or parsed.
+    pub fn try_read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_data_path(year, ANSWERS_FILE_NAME);
+
+        if !Path::new(&path).exists() {
+            return Ok(Answers::default());
+        }
+
+        fs::read_to_string(&path)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .map_err(|e| format!("could not read \"{path}\": {e}"))
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day)
+    }
+
+    /// Records the correct answer for one part of a day, overwriting a previous answer.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.len() - 1
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => entry.part_1 = Some(answer.to_string()),
+            2 => entry.part_2 = Some(answer.to_string()),
+            _ => {}
+        }
+
+        self.data.sort_unstable_by_key(|a| a.day);
+    }
+
+    /// Returns the answer a part is expected to produce: the accepted answer from the ledger if there is one,
+    /// otherwise the answer recorded in `answers.json`.
+    pub fn expected<'a>(&'a self, day: Day, part: u8, ledger: &'a Ledger) -> Option<&'a str> {
+        ledger
+            .accepted(day, part)
+            .or_else(|| self.get(day).and_then(|a| a.part(part)))
+    }
+
+    /// Returns every part whose recorded answer disagrees with the ledger.
+    pub fn conflicts(&self, ledger: &Ledger) -> Vec<Conflict> {
+        self.data
+            .iter()
+            .flat_map(|answer| [1, 2].map(|part| (answer, part)))
+            .filter_map(|(answer, part)| {
+                let recorded = answer.part(part)?;
+                let accepted = ledger.accepted(answer.day, part)?;
+                (recorded != accepted).then(|| Conflict {
+                    day: answer.day,
+                    part,
+                    recorded: recorded.to_string(),
+                    accepted: accepted.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Conflict};
+    use crate::day;
+    use crate::template::aoc_cli::SubmissionOutcome;
+    use crate::template::ledger::Ledger;
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(2), 2, "20");
+        answers.set(day!(1), 1, "10");
+        answers.set(day!(2), 1, "15");
+        answers.set(day!(2), 1, "16");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(2)).unwrap().part(1), Some("16"));
+        assert_eq!(answers.get(day!(2)).unwrap().part(2), Some("20"));
+        assert_eq!(answers.get(day!(1)).unwrap().part(2), None);
+    }
+
+    #[test]
+    fn prefers_accepted_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "10");
+        answers.set(day!(1), 2, "20");
+        answers.set(day!(2), 1, "30");
+
+        let mut ledger = Ledger::default();
+        ledger.record(day!(1), 1, "11", &SubmissionOutcome::Correct);
+        ledger.record(day!(1), 2, "20", &SubmissionOutcome::Correct);
+        ledger.record(day!(3), 1, "40", &SubmissionOutcome::Correct);
+
+        assert_eq!(answers.expected(day!(1), 1, &ledger), Some("11"));
+        assert_eq!(answers.expected(day!(2), 1, &ledger), Some("30"));
+        assert_eq!(answers.expected(day!(3), 1, &ledger), Some("40"));
+        assert_eq!(answers.expected(day!(3), 2, &ledger), None);
+
+        assert_eq!(
+            answers.conflicts(&ledger),
+            [Conflict {
+                day: day!(1),
+                part: 1,
+                recorded: "10".into(),
+                accepted: "11".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "10");
+        answers.set(day!(3), 2, "30");
+
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+
+        assert_eq!(parsed.data, answers.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": 1 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
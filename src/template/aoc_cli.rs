@@ -1,125 +1,537 @@
-/// Wrapper module around the "aoc-cli" command-line.
+/// Native client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers using a session cookie.
 use std::{
+    env,
     fmt::Display,
-    process::{Command, Output, Stdio},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
-use crate::template::Day;
+use crate::template::{get_data_path, Day, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/fspoettel/advent-of-code-rust)"
+);
 
 #[derive(Debug)]
 pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
+    SessionNotFound,
+    BadStatus(u16),
+    Transport(String),
+    IO(io::Error),
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::SessionNotFound => write!(
+                f,
+                "session cookie not found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocCommandError::BadStatus(400) => write!(
+                f,
+                "advent of code rejected the request (400). Is the session cookie still valid?"
+            ),
+            AocCommandError::BadStatus(404) => write!(
+                f,
+                "advent of code returned 404. Is the puzzle unlocked yet?"
+            ),
+            AocCommandError::BadStatus(status) => {
+                write!(f, "advent of code responded with status {status}.")
             }
+            AocCommandError::Transport(e) => write!(f, "request failed: {e}"),
+            AocCommandError::IO(e) => write!(f, "{e}"),
         }
     }
 }
 
-pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+impl From<io::Error> for AocCommandError {
+    fn from(e: io::Error) -> Self {
+        AocCommandError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocCommandError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocCommandError::BadStatus(status),
+            ureq::Error::Transport(t) => AocCommandError::Transport(t.to_string()),
+        }
+    }
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// Hint returned by advent of code alongside a wrong answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+/// Classification of the response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    TooRecent,
+    WrongLevel,
+    Unknown,
+}
+
+/// Response to a submitted answer, with the message shown by the website.
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub outcome: SubmissionOutcome,
+    pub message: String,
+}
+
+/// An HTTP client for a single year of advent of code.
+///
+/// The base url defaults to `https://adventofcode.com` and can be overridden with `AOC_BASE_URL`,
+/// e.g. to point the client to a local stand-in server.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent,
+        }
+    }
+
+    /// Creates a client for a year from `AOC_BASE_URL` and the session cookie.
+    /// The session is read from `AOC_SESSION` or the file `~/.adventofcode.session`.
+    pub fn from_env(year: Year) -> Result<Self, AocCommandError> {
+        let session = get_session().ok_or(AocCommandError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session, year))
+    }
+
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
 
-    call_aoc_cli(&args)
+    /// Fetches the puzzle input for a day.
+    pub fn input(&self, day: Day) -> Result<String, AocCommandError> {
+        let response = self
+            .agent
+            .get(&format!("{}/input", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the puzzle description for a day.
+    /// Returns the html of all unlocked parts, i.e. the `<article>` elements of the puzzle page.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocCommandError> {
+        let response = self
+            .agent
+            .get(&self.day_url(day))
+            .set("Cookie", &self.cookie())
+            .call()?;
+        let page = response.into_string()?;
+        Ok(extract_articles(&page).join("\n\n"))
+    }
+
+    /// Submits an answer for one part of a day.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Submission, AocCommandError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        let page = response.into_string()?;
+        Ok(parse_submission(&page))
+    }
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// Checks that a session cookie is configured.
+pub fn check() -> Result<(), AocCommandError> {
+    get_session()
+        .map(|_| ())
+        .ok_or(AocCommandError::SessionNotFound)
+}
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
+pub fn read(year: Year, day: Day) -> Result<String, AocCommandError> {
+    let client = AocClient::from_env(year)?;
+    println!(
+        "Fetching puzzle for day {}, {}...",
+        day.into_inner(),
+        client.year()
     );
 
-    let output = call_aoc_cli(&args)?;
+    let puzzle = client.puzzle(day)?;
+    write_data_file(&get_puzzle_path(year, day), &puzzle)?;
+
+    let text = html_to_text(&puzzle);
+    println!("{text}");
+    Ok(text)
+}
+
+pub fn download(year: Year, day: Day) -> Result<(), AocCommandError> {
+    download_files(year, day, true, true)
+}
+
+/// Downloads the input and/or the puzzle description of a day, leaving the other file untouched.
+pub fn download_files(
+    year: Year,
+    day: Day,
+    input: bool,
+    puzzle: bool,
+) -> Result<(), AocCommandError> {
+    let client = AocClient::from_env(year)?;
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    // fetch both before writing either, so a failed request does not leave a half-downloaded day behind.
+    let puzzle = if puzzle {
+        Some(client.puzzle(day)?)
+    } else {
+        None
+    };
+    let input = if input {
+        Some(client.input(day)?)
+    } else {
+        None
+    };
+
+    if let Some(puzzle) = &puzzle {
+        write_data_file(&puzzle_path, puzzle)?;
+    }
+    if let Some(input) = &input {
+        write_data_file(&input_path, input)?;
+    }
+
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    if input.is_some() {
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
+    if puzzle.is_some() {
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    }
+    Ok(())
+}
+
+pub fn submit(year: Year, day: Day, part: u8, result: &str) -> Result<Submission, AocCommandError> {
+    let submission = AocClient::from_env(year)?.submit(day, part, result)?;
+    println!("{}", submission.message);
+    Ok(submission)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+/// Writes a file in the data directory, creating the directories of a year that was not scaffolded yet.
+fn write_data_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+fn get_input_path(year: Year, day: Day) -> String {
+    get_data_path(year, &format!("inputs/{day}.txt"))
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// The puzzle page is stored as downloaded, so the examples and the title can be extracted from its markup later.
+pub fn get_puzzle_path(year: Year, day: Day) -> String {
+    get_data_path(year, &format!("puzzles/{day}.html"))
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session);
+        }
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+
+    if session.trim().is_empty() {
+        None
+    } else {
+        Some(session)
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
+/* -------------------------------------------------------------------------- */
+
+/// Returns the `<article>` elements of a puzzle page, i.e. the descriptions of its unlocked parts.
+pub fn extract_articles(page: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = page;
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        let end = start + end + "</article>".len();
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
     }
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    articles
+}
 
-    cmd_args
+/// Returns the title of a puzzle page, e.g. `Day 1: Trebuchet?!` for the heading `--- Day 1: Trebuchet?! ---`.
+pub fn extract_title(page: &str) -> Option<String> {
+    let start = page.find("<h2")?;
+    let start = start + page[start..].find('>')? + 1;
+    let end = start + page[start..].find("</h2>")?;
+    let title = decode_entities(page[start..end].trim().trim_matches('-').trim());
+    (!title.is_empty()).then_some(title)
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+fn parse_submission(page: &str) -> Submission {
+    let message = extract_articles(page)
+        .first()
+        .map_or_else(|| html_to_text(page), |article| html_to_text(article));
 
-    if output.status.success() {
-        Ok(output)
+    let outcome = if message.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if message.contains("That's not the right answer") {
+        SubmissionOutcome::Incorrect(if message.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if message.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        })
+    } else if message.contains("You gave an answer too recently") {
+        SubmissionOutcome::TooRecent
+    } else if message.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::WrongLevel
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        SubmissionOutcome::Unknown
+    };
+
+    Submission { outcome, message }
+}
+
+/// Renders the html of a puzzle page as plain text for the terminal.
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag_body) = rest.strip_prefix('<') {
+            let Some(end) = tag_body.find('>') else {
+                break;
+            };
+            let tag = tag_body[..end].trim().to_ascii_lowercase();
+            let name = tag.split_whitespace().next().unwrap_or_default();
+
+            match name {
+                "pre" => in_pre = true,
+                "/pre" => {
+                    in_pre = false;
+                    text.push('\n');
+                }
+                "/h2" | "/p" | "/ul" => text.push_str("\n\n"),
+                "li" => text.push_str("  - "),
+                "/li" => text.push('\n'),
+                "br" | "br/" => text.push('\n'),
+                _ => {}
+            }
+
+            rest = &tag_body[end + 1..];
+            continue;
+        }
+
+        let next_tag = rest.find('<').unwrap_or(rest.len());
+        let content = decode_entities(&rest[..next_tag]);
+
+        if in_pre {
+            text.push_str(&content);
+        } else {
+            let mut last_was_space = text.ends_with([' ', '\n']) || text.is_empty();
+            for c in content.chars() {
+                if c.is_whitespace() {
+                    if !last_was_space {
+                        text.push(' ');
+                    }
+                    last_was_space = true;
+                } else {
+                    text.push(c);
+                    last_was_space = false;
+                }
+            }
+        }
+
+        rest = &rest[next_tag..];
+    }
+
+    text.trim().to_string()
+}
+
+/// Decodes the html entities that occur in puzzle descriptions.
+pub fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::{
+        extract_title, html_to_text, parse_submission, AocClient, Hint, SubmissionOutcome,
+    };
+    use crate::{day, year};
+
+    /// Serves a single request with `body` and returns the raw request it received.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, server) = serve_once("1\n2\n3\n");
+        let client = AocClient::new(&url, "secret\n", year!(2023));
+
+        let input = client.input(day!(1)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1\n2\n3\n");
+        assert!(request.starts_with("GET /2023/day/1/input "));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn fetches_puzzle_articles() {
+        let (url, server) = serve_once(
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1 ---</h2></article><p>Your puzzle answer was <code>42</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2></article></main></html>",
+        );
+        let client = AocClient::new(&url, "secret", year!(2023));
+
+        let puzzle = client.puzzle(day!(1)).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(
+            puzzle,
+            "<article class=\"day-desc\"><h2>--- Day 1 ---</h2></article>\n\n<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2></article>"
+        );
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (url, server) = serve_once(
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret", year!(2023));
+
+        let submission = client.submit(day!(4), 2, "1234").unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.starts_with("POST /2023/day/4/answer "));
+        assert!(request.ends_with("level=2&answer=1234"));
+        assert_eq!(submission.outcome, SubmissionOutcome::Correct);
+    }
+
+    #[test]
+    fn parses_submission_hints() {
+        let submission = parse_submission(
+            "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p></article>",
+        );
+        assert_eq!(
+            submission.outcome,
+            SubmissionOutcome::Incorrect(Some(Hint::TooHigh))
+        );
+
+        let submission = parse_submission(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 31s left to wait.</p></article>",
+        );
+        assert_eq!(submission.outcome, SubmissionOutcome::TooRecent);
+    }
+
+    #[test]
+    fn renders_html_as_text() {
+        let text = html_to_text(
+            "<article><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em>\nwith &quot;global&quot; snow.</p><pre><code>1abc2\npqr3stu8vwx\n</code></pre><ul><li>one</li><li>two</li></ul></article>",
+        );
+        assert_eq!(
+            text,
+            "--- Day 1: Trebuchet?! ---\n\nSomething is wrong with \"global\" snow.\n\n1abc2\npqr3stu8vwx\n\n  - one\n  - two"
+        );
+    }
+
+    #[test]
+    fn extracts_title() {
+        assert_eq!(
+            extract_title("<article class=\"day-desc\"><h2>--- Day 7: Camel Cards ---</h2><p>Your all-expenses-paid trip &amp; more</p></article>"),
+            Some("Day 7: Camel Cards".into())
+        );
+        assert_eq!(extract_title("<article><p>Nothing</p></article>"), None);
     }
 }
@@ -1,5 +1,30 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::run_multi::{run_multi, Timeouts};
+use crate::template::runner::BenchConfig;
+use crate::template::{all_days, Year};
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    run_multi(&all_days().collect(), is_release, is_timed);
+/// Time limits in seconds, see [`Timeouts::new`].
+pub struct TimeoutOptions {
+    pub part: Option<f64>,
+    pub day: Option<f64>,
+}
+
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    is_timed: bool,
+    budget: Option<u64>,
+    is_isolated: bool,
+    timeouts: &TimeoutOptions,
+) {
+    let bench =
+        is_timed.then(|| budget.map_or_else(BenchConfig::default, BenchConfig::from_budget_millis));
+    let timeouts = Timeouts::new(timeouts.part, timeouts.day);
+    run_multi(
+        year,
+        &all_days().collect(),
+        is_release,
+        bench,
+        is_isolated,
+        timeouts,
+    );
 }
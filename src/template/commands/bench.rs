@@ -0,0 +1,91 @@
+use std::{process, time::Duration};
+
+use crate::template::commands::all::TimeoutOptions;
+use crate::template::run_multi::{is_abandoned, print_skipped, run_in_process, Timeouts};
+use crate::template::runner::{part_label, BenchConfig, PartResult, PARSE};
+use crate::template::{registry, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Benchmark every registered day of a year in-process and print a summary.
+pub fn handle(year: Year, day: Option<Day>, budget: Option<u64>, timeouts: &TimeoutOptions) {
+    let config = budget.map_or_else(BenchConfig::default, BenchConfig::from_budget_millis);
+    let timeouts = Timeouts::new(timeouts.part, timeouts.day);
+
+    if registry::solutions().is_empty() {
+        eprintln!(
+            "No solutions are compiled into this binary. Benchmarks run in-process and need the `in-process` feature, e.g. `cargo benchmark`."
+        );
+        process::exit(1);
+    }
+
+    let mut summary: Vec<(Day, Vec<PartResult>)> = vec![];
+    let mut abandoned: Option<Day> = None;
+    let mut skipped: Vec<Day> = vec![];
+
+    for solution in registry::solutions_for(year).filter(|s| day.is_none_or(|day| s.day == day)) {
+        if !summary.is_empty() || !skipped.is_empty() {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", solution.day);
+        println!("------");
+
+        if let Some(results) = run_in_process(year, solution.day, Some(config), timeouts) {
+            // the worker of a timed-out day keeps running, which skews the benchmarks of later days.
+            if abandoned.is_some() {
+                skipped.push(solution.day);
+                continue;
+            }
+            if is_abandoned(&results) {
+                abandoned = Some(solution.day);
+            }
+            summary.push((solution.day, results));
+        }
+    }
+
+    if let Some(day) = abandoned {
+        print_skipped(day, &skipped);
+    }
+
+    if summary.is_empty() {
+        println!("No solutions to benchmark.");
+        return;
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+
+    let mut total = Duration::ZERO;
+
+    for (day, results) in &summary {
+        let parts = results
+            .iter()
+            .map(|r| {
+                let label = part_label(r.part);
+
+                if let Some(failure) = &r.failure {
+                    return format!("{label}: ✖ {failure}");
+                }
+
+                if r.part != PARSE && r.answer.is_none() {
+                    return format!("{label}: ✖");
+                }
+
+                total += r.duration;
+                let s = &r.stats;
+                format!(
+                    "{label}: {:.1?} ± {:.1?} (p95 {:.1?}, min {:.1?}, max {:.1?}) @ {} samples, {} outliers",
+                    s.median, s.stddev, s.p95, s.min, s.max, s.samples, s.outliers
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        println!("Day {day}: {parts}");
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+        total.as_secs_f64() * 1000_f64
+    );
+}
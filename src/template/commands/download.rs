@@ -1,14 +1,13 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, examples, Day, Year};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(year: Year, day: Day) {
+    if let Err(e) = aoc_cli::download(year, day) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
     };
+
+    if let Err(e) = examples::fill_from_puzzle(year, day) {
+        eprintln!("failed to extract examples from puzzle: {e}");
+    }
 }
@@ -1,6 +1,11 @@
 pub mod all;
+pub mod bench;
 pub mod download;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod verify;
+pub mod watch;
@@ -0,0 +1,15 @@
+use std::process;
+
+use crate::template::readme;
+use crate::template::timings::Timings;
+use crate::template::Year;
+
+pub fn handle(year: Year) {
+    match readme::update(year, &Timings::read_from_file(year)) {
+        Ok(()) => println!("Updated progress and benchmarks in README.md."),
+        Err(e) => {
+            eprintln!("Failed to update README.md: {e}");
+            process::exit(1);
+        }
+    }
+}
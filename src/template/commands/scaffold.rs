@@ -1,28 +1,173 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::aoc_cli::{self, extract_title, get_puzzle_path};
+use crate::template::examples::{self, Manifest};
+use crate::template::module_template::{ModuleTemplate, Placeholders, DEFAULT_TEMPLATE};
+use crate::template::{get_data_path, get_path_for_bin, Day, Year};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Options of `cargo scaffold`.
+pub struct ScaffoldOptions {
+    /// Name of the template in `src/templates` to create the solution from.
+    pub template: Option<String>,
+    /// Download the input and puzzle description before the solution is created, so the template can use its title.
+    pub download: bool,
+    /// Keep an existing solution and only create the missing files of a day.
+    pub repair: bool,
+    /// Replace an existing solution with a new one from the template, after backing it up.
+    pub force: bool,
+}
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Creates an empty file unless it already exists. Returns whether the file was created.
+fn create_if_missing(path: &str) -> Result<bool, std::io::Error> {
+    match safe_create_file(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Returns whether `--download` should fetch the data file at `path`. Repairing a day only fills in files that are missing or empty.
+fn needs_download(path: &str, repair: bool) -> bool {
+    !repair || fs::metadata(path).map_or(true, |metadata| metadata.len() == 0)
+}
+
+pub fn handle(year: Year, day: Day, options: ScaffoldOptions) {
+    let input_path = get_data_path(year, &format!("inputs/{day}.txt"));
+    let module_path = get_path_for_bin(year, day);
+
+    let template =
+        match ModuleTemplate::load(options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE)) {
+            Ok(template) => template,
+            Err(e) => {
+                eprintln!("Failed to load template: {e}");
+                process::exit(1);
+            }
+        };
+
+    let module_exists = Path::new(&module_path).exists();
+    if module_exists && !options.repair && !options.force {
+        eprintln!(
+            "Failed to create module file: \"{module_path}\" already exists. \
+            Use `--repair` to create the missing files of day {day}, or `--force` to replace the module."
+        );
+        process::exit(1);
+    }
+
+    for folder in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(get_data_path(year, folder)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    let mut is_download_failed = false;
+    if options.download {
+        let input = needs_download(&input_path, options.repair);
+        let puzzle = needs_download(&get_puzzle_path(year, day), options.repair);
+        if !input && !puzzle {
+            println!("Found input and puzzle of day {day}, skipping the download");
+        } else if let Err(e) = aoc_cli::download_files(year, day, input, puzzle) {
+            eprintln!("failed to download puzzle: {e}");
+            is_download_failed = true;
+        }
+    }
+
+    match create_if_missing(&input_path) {
+        Ok(true) => println!("Created empty input file \"{}\"", &input_path),
+        Ok(false) => println!("Found input file \"{}\"", &input_path),
+        Err(e) => {
+            eprintln!("Failed to create input file: {e}");
+            process::exit(1);
+        }
+    }
+
+    let manifest_path = Manifest::path(year, day);
+    let manifest = match Manifest::read_from_file(year, day) {
+        Ok(manifest) => {
+            println!("Found example manifest \"{}\"", &manifest_path);
+            manifest
+        }
+        Err(e) if Path::new(&manifest_path).exists() => {
+            eprintln!("Failed to read example manifest: {e}");
+            process::exit(1);
+        }
+        Err(_) => {
+            let manifest = match template.examples {
+                2 => Manifest::per_part(day),
+                _ => Manifest::empty(day),
+            };
+            match manifest.store_file(year, day) {
+                Ok(()) => {
+                    println!("Created example manifest \"{}\"", &manifest_path);
+                }
+                Err(e) => {
+                    eprintln!("Failed to create example manifest: {e}");
+                    process::exit(1);
+                }
+            }
+            manifest
+        }
+    };
+
+    for file in manifest.files() {
+        let example_path = get_data_path(year, &format!("examples/{file}"));
+        match create_if_missing(&example_path) {
+            Ok(true) => println!("Created empty example file \"{}\"", &example_path),
+            Ok(false) => println!("Found example file \"{}\"", &example_path),
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if let Err(e) = examples::fill_from_puzzle(year, day) {
+        eprintln!("Failed to extract examples from puzzle: {e}");
+    }
+
+    if module_exists && !options.force {
+        repair_module(&module_path);
+    } else {
+        if module_exists {
+            backup_module(&module_path);
+        }
+
+        let placeholders = Placeholders {
+            year,
+            day,
+            title: fs::read_to_string(get_puzzle_path(year, day))
+                .ok()
+                .and_then(|puzzle| extract_title(&puzzle)),
+            examples: Manifest::read_from_file(year, day)
+                .unwrap_or(manifest)
+                .files()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        };
+
+        create_module(&module_path, &template, &placeholders);
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
 
-    let mut file = match safe_create_file(&module_path) {
+    if is_download_failed {
+        process::exit(1);
+    }
+}
+
+fn create_module(module_path: &str, template: &ModuleTemplate, placeholders: &Placeholders) {
+    let mut file = match safe_create_file(module_path) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -30,40 +175,168 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(template.render(placeholders).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!(
+                "Created module file \"{}\" from template `{}`",
+                module_path, template.name
+            );
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+/// Moves an existing module to the first free backup path, e.g. `2023_05.rs.bak`.
+fn backup_module(module_path: &str) {
+    let backup_path = (1..)
+        .map(|n| match n {
+            1 => format!("{module_path}.bak"),
+            n => format!("{module_path}.bak{n}"),
+        })
+        .find(|path| !Path::new(path).exists())
+        .unwrap();
+
+    match fs::rename(module_path, &backup_path) {
+        Ok(()) => println!("Moved existing module file to \"{backup_path}\""),
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to back up module file: {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+/// Reports whether an existing module is registered and tested, and adds its example tests if they are missing.
+/// The example tests rely on the functions `solution!` generates, so an unregistered module is left alone.
+fn repair_module(module_path: &str) {
+    let source = match fs::read_to_string(module_path) {
+        Ok(source) => source,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to read module file: {e}");
             process::exit(1);
         }
+    };
+
+    let status = ModuleStatus::of(&source);
+    println!(
+        "Found module file \"{module_path}\" ({}, {})",
+        if status.is_registered {
+            "registered"
+        } else {
+            "not registered"
+        },
+        if status.is_tested {
+            "tested"
+        } else {
+            "not tested"
+        }
+    );
+
+    if !status.is_registered {
+        eprintln!(
+            "The module does not call `advent_of_code::solution!`, which registers the day. \
+            Add it by hand, or use `--force` to replace the module."
+        );
+        if !status.is_tested {
+            eprintln!(
+                "Example tests were not added, as they need `advent_of_code::solution!` to be called first."
+            );
+        }
+        return;
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if !status.is_tested {
+        let tests = format!(
+            "{}\nadvent_of_code::example_tests!();\n",
+            if source.ends_with('\n') { "" } else { "\n" }
+        );
+        match OpenOptions::new()
+            .append(true)
+            .open(module_path)
+            .and_then(|mut file| file.write_all(tests.as_bytes()))
+        {
+            Ok(()) => println!("Added example tests to \"{module_path}\""),
+            Err(e) => {
+                eprintln!("Failed to add example tests: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ModuleStatus {
+    /// The module calls `solution!`, which adds the day to the solution registry.
+    is_registered: bool,
+    /// The module calls `example_tests!`, which tests the day against its examples.
+    is_tested: bool,
+}
+
+impl ModuleStatus {
+    fn of(source: &str) -> Self {
+        let calls = |name: &str| {
+            source
+                .lines()
+                .map(str::trim_start)
+                .filter(|line| !line.starts_with("//"))
+                .any(|line| line.contains(&format!("{name}!(")))
+        };
+
+        Self {
+            is_registered: calls("solution"),
+            is_tested: calls("example_tests"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{needs_download, ModuleStatus};
+
+    #[test]
+    fn checks_module_status() {
+        assert_eq!(
+            ModuleStatus::of(
+                "advent_of_code::solution!(5);\n\nfn part_one() {}\n\nadvent_of_code::example_tests!();\n"
+            ),
+            ModuleStatus {
+                is_registered: true,
+                is_tested: true,
+            }
+        );
+        assert_eq!(
+            ModuleStatus::of(
+                "// advent_of_code::example_tests!();\nsolution!(5, parse = parse);\n"
+            ),
+            ModuleStatus {
+                is_registered: true,
+                is_tested: false,
+            }
+        );
+    }
+
+    #[test]
+    fn repairs_only_download_missing_files() {
+        let path = env::temp_dir().join(format!("aoc-test-input-{}.txt", process::id()));
+        let path = path.to_string_lossy().to_string();
+        let _ = fs::remove_file(&path);
+
+        assert!(needs_download(&path, true));
+        assert!(needs_download(&path, false));
+
+        fs::write(&path, "").unwrap();
+        assert!(needs_download(&path, true));
+
+        fs::write(&path, "my input").unwrap();
+        assert!(!needs_download(&path, true));
+        assert!(needs_download(&path, false));
+
+        fs::remove_file(&path).unwrap();
+    }
 }
@@ -1,9 +1,33 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::input::InputSource;
+use crate::template::{get_bin_name, Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Options of `cargo solve`, passed through to the solution binary.
+pub struct SolveOptions {
+    pub release: bool,
+    pub time: bool,
+    pub budget: Option<u64>,
+    pub dhat: bool,
+    pub submit: Option<u8>,
+    pub input: Option<InputSource>,
+}
+
+pub fn handle(year: Year, day: Day, options: SolveOptions) {
+    let SolveOptions {
+        release,
+        time,
+        budget,
+        dhat,
+        submit: submit_part,
+        input,
+    } = options;
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        get_bin_name(year, day),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -27,6 +51,16 @@ pub fn handle(day: Day, release: bool, time: bool, dhat: bool, submit_part: Opti
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(budget) = budget {
+        cmd_args.push("--budget".to_string());
+        cmd_args.push(budget.to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
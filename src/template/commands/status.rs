@@ -0,0 +1,63 @@
+use std::io::{self, IsTerminal};
+use std::{env, process};
+
+use crate::template::answers::Answers;
+use crate::template::ledger::Ledger;
+use crate::template::run_multi::child_commands;
+use crate::template::status::{self, DayStatus};
+use crate::template::timings::Timings;
+use crate::template::{all_days, Year};
+
+/// Options of `cargo status`.
+pub struct StatusOptions {
+    /// Print the status as JSON instead of a table.
+    pub json: bool,
+    /// Skip running the example tests of every scaffolded day, which is the slowest part of the status.
+    pub no_tests: bool,
+}
+
+pub fn handle(year: Year, options: StatusOptions) {
+    let answers = Answers::read_from_file(year);
+    let ledger = Ledger::read_from_file(year);
+    let timings = Timings::read_from_file(year);
+    let show_progress = !options.json && !options.no_tests && io::stderr().is_terminal();
+
+    let statuses: Vec<DayStatus> = all_days()
+        .map(|day| {
+            let mut status = DayStatus::read(year, day, &answers, &ledger, &timings);
+
+            if status.is_scaffolded && !options.no_tests {
+                if show_progress {
+                    eprint!("\rTesting day {day}...");
+                }
+                status.tests = match child_commands::test_solution(year, day) {
+                    Ok(output) => Some(output.status.success()),
+                    Err(e) => {
+                        eprintln!("\nFailed to run tests of day {day}: {e}");
+                        None
+                    }
+                };
+            }
+
+            status
+        })
+        .collect();
+
+    if options.json {
+        match status::to_json(year, &statuses).format() {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("Failed to serialize status: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if show_progress {
+        eprint!("\r\x1b[K");
+    }
+
+    let is_colored = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    println!("{}", status::render_table(&statuses, is_colored));
+}
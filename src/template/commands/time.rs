@@ -1,15 +1,33 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::commands::all::TimeoutOptions;
+use crate::template::compare;
+pub use crate::template::compare::DEFAULT_THRESHOLD;
+use crate::template::run_multi::{run_multi, Timeouts};
+use crate::template::runner::BenchConfig;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme, Day, Year};
 
-pub fn handle(day: Option<Day>, recreate_all: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options of `cargo time --compare`.
+pub struct CompareOptions {
+    /// Relative slowdown in percent above which a significant change counts as a regression.
+    pub threshold: f64,
+}
+
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    recreate_all: bool,
+    budget: Option<u64>,
+    is_isolated: bool,
+    compare: Option<CompareOptions>,
+    timeouts: &TimeoutOptions,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
-            if recreate_all {
+            if recreate_all || compare.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,18 +39,35 @@ pub fn handle(day: Option<Day>, recreate_all: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if compare.is_some() {
+        // keep the previous timings around, so the comparison can be inspected later.
+        if let Err(e) = stored_timings.store_baseline(year) {
+            eprintln!("Failed to store baseline timings: {e}");
+        }
+    }
+
+    let bench = budget.map_or_else(BenchConfig::default, BenchConfig::from_budget_millis);
+    let timeouts = Timeouts::new(timeouts.part, timeouts.day);
+    let timings = run_multi(year, &days_to_run, true, Some(bench), is_isolated, timeouts).unwrap();
 
     let merged_timings = stored_timings.merge(&timings);
-    merged_timings.store_file().unwrap();
+    merged_timings.store_file(year).unwrap();
 
     println!();
-    match readme_benchmarks::update(merged_timings) {
+    match readme::update(year, &merged_timings) {
         Ok(()) => {
             println!("Stored updated benchmarks.");
         }
-        Err(_) => {
-            eprintln!("Failed to store updated benchmarks.");
+        Err(e) => {
+            eprintln!("Failed to store updated benchmarks: {e}");
+        }
+    }
+
+    if let Some(options) = compare {
+        println!();
+        let comparisons = compare::compare(&stored_timings, &timings);
+        if compare::report(&comparisons, options.threshold) > 0 {
+            process::exit(1);
         }
     }
 }
@@ -0,0 +1,143 @@
+use std::{collections::HashSet, process};
+
+use crate::template::answers::Answers;
+use crate::template::ledger::Ledger;
+use crate::template::run_multi::{child_commands, Timeouts};
+use crate::template::{all_days, get_path_for_bin, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(year: Year, day: Option<Day>, record: bool) {
+    let (mut answers, ledger) = match Answers::try_read_from_file(year)
+        .and_then(|answers| Ledger::try_read_from_file(year).map(|ledger| (answers, ledger)))
+    {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Failed to read recorded answers: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| {
+                    record
+                        || [1, 2]
+                            .iter()
+                            .any(|part| answers.expected(*day, *part, &ledger).is_some())
+                })
+                .collect()
+        },
+        |day| HashSet::from([day]),
+    );
+
+    let mut mismatches = 0;
+    let mut failures = 0;
+
+    if !record {
+        for conflict in answers
+            .conflicts(&ledger)
+            .iter()
+            .filter(|conflict| days_to_run.contains(&conflict.day))
+        {
+            println!(
+                "✖ Day {} part {}: answers.json records `{}`, but advent of code accepted `{}`. Verifying against the accepted answer.",
+                conflict.day, conflict.part, conflict.recorded, conflict.accepted
+            );
+            mismatches += 1;
+        }
+    }
+
+    // NOTE: use non-duplicate, sorted day values.
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if !std::path::Path::new(&get_path_for_bin(year, day)).exists() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let results = match child_commands::run_solution(year, day, None, true, Timeouts::default())
+        {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("Failed to run solution: {e}");
+                failures += 1;
+                println!();
+                continue;
+            }
+        };
+
+        for part in 1..=2 {
+            let actual = results
+                .iter()
+                .find(|r| r.part == part)
+                .and_then(|r| r.answer.clone());
+
+            if record {
+                match (actual, ledger.accepted(day, part)) {
+                    (Some(actual), Some(accepted)) if actual != accepted => {
+                        println!("✖ Part {part}: got `{actual}`, but advent of code accepted `{accepted}`. Not recorded.");
+                        mismatches += 1;
+                    }
+                    (Some(actual), _) => answers.set(day, part, &actual),
+                    (None, _) => {}
+                }
+                continue;
+            }
+
+            let Some(expected) = answers.expected(day, part, &ledger) else {
+                continue;
+            };
+
+            match actual {
+                Some(actual) if actual == expected => {
+                    println!("✔ Part {part} matches recorded answer.");
+                }
+                Some(actual) => {
+                    println!("✖ Part {part}: expected `{expected}`, got `{actual}`.");
+                    mismatches += 1;
+                }
+                None => {
+                    println!("✖ Part {part}: expected `{expected}`, got no answer.");
+                    mismatches += 1;
+                }
+            }
+        }
+
+        println!();
+    }
+
+    if record {
+        match answers.store_file(year) {
+            Ok(()) => println!("Stored recorded answers."),
+            Err(e) => {
+                eprintln!("Failed to store recorded answers: {e}");
+                process::exit(1);
+            }
+        }
+        if failures > 0 {
+            eprintln!("{failures} day(s) failed to run, their answers were not recorded.");
+        }
+        if mismatches > 0 {
+            eprintln!(
+                "{mismatches} answer(s) disagreed with accepted answers and were not recorded."
+            );
+        }
+        if failures > 0 || mismatches > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if failures > 0 {
+        eprintln!("{failures} day(s) failed to run.");
+    }
+    if mismatches > 0 {
+        eprintln!("{mismatches} answer(s) did not match the recorded answers.");
+    }
+    if failures > 0 || mismatches > 0 {
+        process::exit(1);
+    }
+
+    println!("All recorded answers match.");
+}
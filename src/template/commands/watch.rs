@@ -0,0 +1,267 @@
+/// Watch mode: re-runs the example tests and the solution of a day whenever one of its files changes.
+/// Changes are detected by polling the modification time and size of every watched file.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::run_multi::{child_commands, Timeouts};
+use crate::template::{
+    get_data_path, get_path_for_bin, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Editors often write a file in several steps, so a change only counts once the files stop changing.
+const SETTLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The modification time and size of every watched file that exists.
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+pub fn handle(year: Year, day: Day, is_release: bool) {
+    if !Path::new(&get_path_for_bin(year, day)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet. Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    }
+
+    println!("Watching day {day}, press Ctrl-C to stop.");
+
+    let mut snapshot = take_snapshot(year, day);
+    run(year, day, is_release, &[]);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let mut current = take_snapshot(year, day);
+        if current == snapshot {
+            continue;
+        }
+
+        loop {
+            thread::sleep(SETTLE_INTERVAL);
+            let settled = take_snapshot(year, day);
+            if settled == current {
+                break;
+            }
+            current = settled;
+        }
+
+        let changed = changed_files(&snapshot, &current);
+        snapshot = current;
+        run(year, day, is_release, &changed);
+    }
+}
+
+/// The solution file of a day, its puzzle input and all of its example files, e.g. `05.txt`, `05-2.txt` and `05.json`.
+fn watched_files(year: Year, day: Day) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(get_path_for_bin(year, day)),
+        PathBuf::from(get_data_path(year, &format!("inputs/{day}.txt"))),
+    ];
+
+    if let Ok(entries) = fs::read_dir(get_data_path(year, "examples")) {
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&day.to_string()))
+                }),
+        );
+    }
+
+    files
+}
+
+fn take_snapshot(year: Year, day: Day) -> Snapshot {
+    watched_files(year, day)
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            Some((path, (metadata.modified().ok()?, metadata.len())))
+        })
+        .collect()
+}
+
+/// Returns the files that were created, changed or removed between two snapshots.
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, state)| before.get(*path) != Some(state))
+        .chain(before.iter().filter(|(path, _)| !after.contains_key(*path)))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.sort();
+    changed
+}
+
+fn run(year: Year, day: Day, is_release: bool, changed: &[PathBuf]) {
+    println!();
+    if changed.is_empty() {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    } else {
+        let names: Vec<String> = changed.iter().map(|p| p.display().to_string()).collect();
+        println!("{ANSI_BOLD}Changed:{ANSI_RESET} {}", names.join(", "));
+    }
+    println!("------");
+
+    if !run_tests(year, day) {
+        println!("{ANSI_ITALIC}Fix the examples to run the puzzle input.{ANSI_RESET}");
+        return;
+    }
+
+    if !Path::new(&get_data_path(year, &format!("inputs/{day}.txt"))).exists() {
+        println!("Input: missing. Run `cargo download {day}` to fetch it.");
+        return;
+    }
+
+    println!("Input:");
+    if let Err(e) =
+        child_commands::run_solution(year, day, None, is_release, Timeouts::new(None, None))
+    {
+        eprintln!("Failed to run solution: {e}");
+    }
+}
+
+/// Runs the example tests of a day and prints a line with the outcome of every test.
+/// Returns whether all tests passed.
+fn run_tests(year: Year, day: Day) -> bool {
+    let output = match child_commands::test_solution(year, day) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            return false;
+        }
+    };
+
+    let tests = parse_tests(&String::from_utf8_lossy(&output.stdout));
+
+    if tests.is_empty() && !output.status.success() {
+        println!("Examples: ✖ build failed");
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        return false;
+    }
+
+    let summary: Vec<String> = tests
+        .iter()
+        .map(|test| format!("{} {}", test.label(), if test.passed { "✔" } else { "✖" }))
+        .collect();
+    println!("Examples: {}", summary.join(" · "));
+
+    for test in tests.iter().filter(|test| !test.passed) {
+        for line in &test.details {
+            println!("  {line}");
+        }
+    }
+
+    output.status.success()
+}
+
+#[derive(Debug, PartialEq)]
+struct TestOutcome {
+    name: String,
+    passed: bool,
+    /// The panic message of a failed test.
+    details: Vec<String>,
+}
+
+impl TestOutcome {
+    fn label(&self) -> String {
+        match self.name.as_str() {
+            "example_tests::part_one" => "Part 1".into(),
+            "example_tests::part_two" => "Part 2".into(),
+            name => name.into(),
+        }
+    }
+}
+
+/// Reads the outcome of every test from the output of the test harness in its `pretty` format.
+fn parse_tests(output: &str) -> Vec<TestOutcome> {
+    let mut tests: Vec<TestOutcome> = output
+        .lines()
+        .filter_map(|line| {
+            let (name, result) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+            let passed = match result {
+                "ok" => true,
+                "FAILED" => false,
+                _ => return None,
+            };
+            Some(TestOutcome {
+                name: name.to_string(),
+                passed,
+                details: vec![],
+            })
+        })
+        .collect();
+
+    // failures are listed in sections like `---- example_tests::part_one stdout ----`.
+    let mut current: Option<usize> = None;
+    for line in output.lines() {
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" stdout ----"))
+        {
+            current = tests.iter().position(|test| test.name == name);
+            continue;
+        }
+
+        let Some(index) = current else {
+            continue;
+        };
+
+        if line.starts_with("note: ") || line == "failures:" {
+            current = None;
+        } else if !line.trim().is_empty() && !line.starts_with("thread '") {
+            tests[index].details.push(line.to_string());
+        }
+    }
+
+    tests
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_tests;
+
+    #[test]
+    fn parses_test_output() {
+        let output = [
+            "running 2 tests",
+            "test example_tests::part_one ... ok",
+            "test example_tests::part_two ... FAILED",
+            "",
+            "failures:",
+            "",
+            "---- example_tests::part_two stdout ----",
+            "",
+            "thread 'example_tests::part_two' panicked at src/template/examples.rs:101:5:",
+            "part 2 failed 1 of 1 example(s):",
+            "example `05.txt`: expected `46`, got `45`",
+            "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+            "",
+            "failures:",
+            "    example_tests::part_two",
+        ]
+        .join("\n");
+
+        let tests = parse_tests(&output);
+        assert_eq!(tests.len(), 2);
+        assert!(tests[0].passed && tests[0].details.is_empty());
+        assert_eq!(tests[1].label(), "Part 2");
+        assert!(!tests[1].passed);
+        assert_eq!(
+            tests[1].details,
+            [
+                "part 2 failed 1 of 1 example(s):",
+                "example `05.txt`: expected `46`, got `45`"
+            ]
+        );
+    }
+}
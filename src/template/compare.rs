@@ -0,0 +1,274 @@
+/// Module that compares benchmark timings against a baseline.
+/// Differences are tested for significance with Welch's t-test on the stored sample statistics.
+use std::{fmt::Display, time::Duration};
+
+use crate::template::runner::{Failure, PARSE};
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Default regression threshold in percent.
+pub const DEFAULT_THRESHOLD: f64 = 5.0;
+
+/// Critical value of the t-distribution for a two-sided test at roughly 95% confidence.
+/// Benchmarks take at least ten samples, so the normal approximation is good enough.
+const CRITICAL_T: f64 = 2.0;
+
+/// The comparison of one part of a day against its baseline. Part [`PARSE`] compares the day's `parse` function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Relative change of the median in percent. Positive values are slowdowns.
+    pub delta_percent: f64,
+    pub is_significant: bool,
+    /// Why the part has no current timing, e.g. because it timed out.
+    pub failure: Option<Failure>,
+}
+
+impl PartComparison {
+    /// A part regresses if it failed, or got significantly slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.failure.is_some() || (self.is_significant && self.delta_percent > threshold)
+    }
+}
+
+impl Display for PartComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self.part {
+            PARSE => "Parse".to_string(),
+            part => format!("Part {part}"),
+        };
+
+        if let Some(failure) = &self.failure {
+            return write!(
+                f,
+                "Day {} {label}: {:.1?} → {failure}",
+                self.day, self.baseline
+            );
+        }
+
+        write!(
+            f,
+            "Day {} {label}: {:.1?} → {:.1?} ({:+.1}%{})",
+            self.day,
+            self.baseline,
+            self.current,
+            self.delta_percent,
+            if self.is_significant {
+                ""
+            } else {
+                ", not significant"
+            }
+        )
+    }
+}
+
+/// Compare all parts of `current` that have statistics in `baseline`.
+/// A part without current statistics is compared as a failure if its day stopped at or before it.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<PartComparison> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| {
+            let previous = baseline.data.iter().find(|t| t.day == timing.day)?;
+            Some((timing, previous))
+        })
+        .flat_map(|(timing, previous)| {
+            [PARSE, 1, 2].into_iter().filter_map(move |part| {
+                let baseline = part_stats(previous, part)?;
+
+                match (part_stats(timing, part), &timing.failure) {
+                    (Some(current), _) => Some(compare_stats(timing.day, part, baseline, current)),
+                    // parts run in order, so the parts after a failed one did not run either.
+                    (None, Some(failure)) if failure.part <= part => Some(PartComparison {
+                        day: timing.day,
+                        part,
+                        baseline: baseline.median,
+                        current: Duration::ZERO,
+                        delta_percent: 0.0,
+                        is_significant: false,
+                        failure: Some(failure.failure.clone()),
+                    }),
+                    _ => None,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Print a comparison report and return the number of regressions past `threshold`.
+pub fn report(comparisons: &[PartComparison], threshold: f64) -> usize {
+    println!("{ANSI_BOLD}Comparison with baseline{ANSI_RESET}");
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No baseline timings to compare against.");
+        return 0;
+    }
+
+    let mut regressions = 0;
+
+    for comparison in comparisons {
+        if comparison.is_regression(threshold) {
+            regressions += 1;
+            println!("✖ {comparison}");
+        } else {
+            println!("  {comparison}");
+        }
+    }
+
+    if regressions > 0 {
+        println!("\n{regressions} part(s) failed or regressed by more than {threshold}%.");
+    }
+
+    regressions
+}
+
+fn part_stats(timing: &Timing, part: u8) -> Option<&Stats> {
+    match part {
+        PARSE => timing.parse_stats.as_ref(),
+        1 => timing.part_1_stats.as_ref(),
+        2 => timing.part_2_stats.as_ref(),
+        _ => None,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn compare_stats(day: Day, part: u8, baseline: &Stats, current: &Stats) -> PartComparison {
+    let nanos = |d: Duration| d.as_nanos() as f64;
+
+    let delta_percent = if baseline.median.is_zero() {
+        0.0
+    } else {
+        (nanos(current.median) - nanos(baseline.median)) / nanos(baseline.median) * 100.0
+    };
+
+    // Welch's t-test on the means of both sample sets.
+    let variance = |s: &Stats| nanos(s.stddev).powi(2) / s.samples.max(1) as f64;
+    let standard_error = (variance(baseline) + variance(current)).sqrt();
+    let difference = nanos(current.mean) - nanos(baseline.mean);
+
+    let is_significant = if standard_error == 0.0 {
+        difference != 0.0
+    } else {
+        (difference / standard_error).abs() > CRITICAL_T
+    };
+
+    PartComparison {
+        day,
+        part,
+        baseline: baseline.median,
+        current: current.median,
+        delta_percent,
+        is_significant,
+        failure: None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::compare;
+    use crate::day;
+    use crate::template::runner::Failure;
+    use crate::template::stats::Stats;
+    use crate::template::timings::{DayFailure, Timing, Timings};
+
+    fn stats(median: u64, stddev: u64) -> Stats {
+        Stats {
+            mean: Duration::from_nanos(median),
+            median: Duration::from_nanos(median),
+            p95: Duration::from_nanos(median + 2 * stddev),
+            min: Duration::from_nanos(median - stddev),
+            max: Duration::from_nanos(median + 3 * stddev),
+            stddev: Duration::from_nanos(stddev),
+            samples: 100,
+            outliers: 0,
+        }
+    }
+
+    fn timings(part_1: Option<Stats>, part_2: Option<Stats>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: part_1.as_ref().map(|s| format!("{:.1?}", s.median)),
+                part_2: part_2.as_ref().map(|s| format!("{:.1?}", s.median)),
+                parse: None,
+                part_1_stats: part_1,
+                part_2_stats: part_2,
+                parse_stats: None,
+                failure: None,
+                total_nanos: 0.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let baseline = timings(Some(stats(1000, 10)), Some(stats(2000, 10)));
+        let current = timings(Some(stats(1200, 10)), Some(stats(2002, 10)));
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 2);
+        assert!(comparisons[0].is_significant);
+        assert!((comparisons[0].delta_percent - 20.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(5.0));
+        assert!(!comparisons[0].is_regression(25.0));
+        assert!(!comparisons[1].is_significant);
+        assert!(!comparisons[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn ignores_noisy_differences() {
+        let baseline = timings(Some(stats(1000, 500)), None);
+        let current = timings(Some(stats(1100, 500)), None);
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 1);
+        assert!(!comparisons[0].is_significant);
+        assert!(!comparisons[0].is_regression(5.0));
+    }
+
+    #[test]
+    fn does_not_flag_improvements() {
+        let baseline = timings(Some(stats(2000, 10)), None);
+        let current = timings(Some(stats(1000, 10)), None);
+        let comparisons = compare(&baseline, &current);
+
+        assert!(comparisons[0].is_significant);
+        assert!(!comparisons[0].is_regression(5.0));
+    }
+
+    #[test]
+    fn skips_parts_without_stats() {
+        let baseline = timings(None, Some(stats(1000, 10)));
+        let current = timings(Some(stats(1000, 10)), None);
+        assert!(compare(&baseline, &current).is_empty());
+        assert!(compare(&Timings::default(), &current).is_empty());
+    }
+
+    #[test]
+    fn flags_failed_parts() {
+        let baseline = timings(Some(stats(1000, 10)), Some(stats(2000, 10)));
+        let mut current = timings(Some(stats(1000, 10)), None);
+        current.data[0].failure = Some(DayFailure {
+            part: 2,
+            failure: Failure::Timeout(Duration::from_secs(60)),
+        });
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].is_regression(5.0));
+        assert!(comparisons[1].is_regression(5.0));
+        assert_eq!(
+            comparisons[1].to_string(),
+            "Day 01 Part 2: 2.0µs → timed out after 60.0s"
+        );
+    }
+}
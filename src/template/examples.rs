@@ -0,0 +1,491 @@
+/// Module that describes the examples of a day and checks solutions against them.
+/// The manifest `data/<year>/examples/<day>.json` lists example files together with the part and expected answer,
+/// so adding an example does not require changes to the solution's tests.
+/// Examples and answers can be filled in from the downloaded puzzle description.
+use std::{collections::HashMap, env, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli::{decode_entities, extract_articles, get_puzzle_path};
+use crate::template::{get_data_path, Day, Year};
+
+/// A single example of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Name of the example file in `data/<year>/examples`, e.g. `08-2.txt`.
+    pub file: String,
+    pub part: u8,
+    /// The expected answer. `None` if the part is expected to return no answer.
+    pub answer: Option<String>,
+}
+
+/// The examples of a day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    /// Creates a manifest with one example file for both parts that expects no answers.
+    pub fn empty(day: Day) -> Self {
+        Self {
+            examples: (1..=2)
+                .map(|part| Example {
+                    file: format!("{day}.txt"),
+                    part,
+                    answer: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Creates a manifest with a separate example file for each part, e.g. `05-1.txt` and `05-2.txt`.
+    pub fn per_part(day: Day) -> Self {
+        Self {
+            examples: (1..=2)
+                .map(|part| Example {
+                    file: format!("{day}-{part}.txt"),
+                    part,
+                    answer: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the distinct example files, in order of their first use.
+    pub fn files(&self) -> Vec<&str> {
+        let mut files: Vec<&str> = vec![];
+        for example in &self.examples {
+            if !files.contains(&example.file.as_str()) {
+                files.push(&example.file);
+            }
+        }
+        files
+    }
+
+    pub fn path(year: Year, day: Day) -> String {
+        get_data_path(year, &format!("examples/{day}.json"))
+    }
+
+    /// Dehydrate the manifest of a day to a JSON file.
+    pub fn store_file(&self, year: Year, day: Day) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Self::path(year, day))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the manifest of a day from a JSON file.
+    pub fn read_from_file(year: Year, day: Day) -> Result<Self, String> {
+        let path = Self::path(year, day);
+        fs::read_to_string(&path)
+            .map_err(|e| format!("could not read \"{path}\": {e}"))
+            .and_then(Manifest::try_from)
+    }
+
+    /// Returns the examples of a part.
+    pub fn part(&self, part: u8) -> impl Iterator<Item = &Example> {
+        self.examples.iter().filter(move |e| e.part == part)
+    }
+}
+
+/// Runs a part against all of its examples and panics with a list of mismatches.
+/// Used by the tests that `example_tests!` generates.
+pub fn check(
+    year: Year,
+    day: Day,
+    part: u8,
+    solve: fn(&str, u8) -> Result<String, Option<String>>,
+) {
+    let manifest = Manifest::read_from_file(year, day).unwrap_or_else(|e| panic!("{e}"));
+    let cwd = env::current_dir().unwrap();
+
+    let mut checked = 0;
+    let mut failures: Vec<String> = vec![];
+
+    for example in manifest.part(part) {
+        let path = cwd.join(get_data_path(year, &format!("examples/{}", example.file)));
+        let input = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not read example \"{}\": {e}", path.display()));
+
+        let actual = solve(&input, part);
+        checked += 1;
+
+        if actual.as_ref().ok() != example.answer.as_ref() {
+            failures.push(format!(
+                "example `{}`: expected {}, got {}",
+                example.file,
+                format_answer(example.answer.as_deref()),
+                match &actual {
+                    Ok(answer) => format_answer(Some(answer)),
+                    Err(Some(error)) => format!("error: {error}"),
+                    Err(None) => format_answer(None),
+                }
+            ));
+        }
+    }
+
+    if checked == 0 {
+        println!(
+            "no examples for part {part} in \"{}\".",
+            Manifest::path(year, day)
+        );
+    }
+
+    assert!(
+        failures.is_empty(),
+        "part {part} failed {} of {checked} example(s):\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+fn format_answer(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) => format!("`{answer}`"),
+        None => "no answer".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The example of a part, as found in the puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleExample {
+    pub part: u8,
+    /// The first code block of the part's description.
+    pub input: Option<String>,
+    /// The last emphasised code of the part's description, which usually is the example's answer.
+    pub answer: Option<String>,
+}
+
+/// Finds the example input and expected answer of each unlocked part in the html of a puzzle description.
+pub fn extract_examples(puzzle: &str) -> Vec<PuzzleExample> {
+    extract_articles(puzzle)
+        .into_iter()
+        .take(2)
+        .zip(1..)
+        .map(|(article, part)| PuzzleExample {
+            part,
+            input: first_code_block(article),
+            answer: last_emphasised_code(article),
+        })
+        .collect()
+}
+
+/// Fills in the example files and expected answers of a day from its downloaded puzzle description.
+/// Does not overwrite example files with content or answers that are already set.
+pub fn fill_from_puzzle(year: Year, day: Day) -> Result<(), Error> {
+    let Ok(puzzle) = fs::read_to_string(get_puzzle_path(year, day)) else {
+        return Ok(());
+    };
+
+    let mut manifest = Manifest::read_from_file(year, day).unwrap_or_default();
+    let mut is_changed = false;
+    let examples = extract_examples(&puzzle);
+
+    for example in &examples {
+        // part two usually reuses the example of part one, unless the manifest gives each part a file of its own.
+        let file = match (own_file(&manifest, example.part), &example.input) {
+            (Some(file), _) => file,
+            (None, Some(input))
+                if example.part == 2 && examples[0].input.as_ref() != Some(input) =>
+            {
+                format!("{day}-2.txt")
+            }
+            _ => format!("{day}.txt"),
+        };
+
+        if let Some(input) = &example.input {
+            let path = get_data_path(year, &format!("examples/{file}"));
+            if fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty()) {
+                fs::write(&path, input)?;
+                println!("Extracted example for part {} to \"{path}\"", example.part);
+            }
+        }
+
+        let Some(answer) = &example.answer else {
+            continue;
+        };
+
+        if manifest.part(example.part).any(|e| e.answer.is_some()) {
+            continue;
+        }
+
+        let entry = match manifest
+            .examples
+            .iter_mut()
+            .find(|e| e.part == example.part)
+        {
+            Some(entry) => entry,
+            None => {
+                manifest.examples.push(Example {
+                    file: file.clone(),
+                    part: example.part,
+                    answer: None,
+                });
+                manifest.examples.last_mut().unwrap()
+            }
+        };
+
+        entry.file = file;
+        entry.answer = Some(answer.clone());
+        is_changed = true;
+        println!(
+            "Extracted expected answer `{answer}` for part {}",
+            example.part
+        );
+    }
+
+    if is_changed {
+        manifest.store_file(year, day)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the example file of a part if no other part uses it.
+fn own_file(manifest: &Manifest, part: u8) -> Option<String> {
+    manifest
+        .part(part)
+        .map(|example| &example.file)
+        .find(|file| {
+            manifest
+                .examples
+                .iter()
+                .all(|e| e.part == part || &e.file != *file)
+        })
+        .cloned()
+}
+
+fn first_code_block(article: &str) -> Option<String> {
+    let start = article.find("<pre><code>")? + "<pre><code>".len();
+    let end = start + article[start..].find("</code></pre>")?;
+    Some(decode_entities(&strip_tags(&article[start..end])))
+}
+
+fn last_emphasised_code(article: &str) -> Option<String> {
+    // NOTE: code blocks may contain emphasis, too, which are not answers.
+    let mut prose = String::new();
+    let mut rest = article;
+    while let Some(start) = rest.find("<pre>") {
+        prose.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("</pre>")
+            .map_or("", |end| &rest[start + end + "</pre>".len()..]);
+    }
+    prose.push_str(rest);
+
+    [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ]
+    .iter()
+    .filter_map(|(open, close)| {
+        let start = prose.rfind(open)? + open.len();
+        let end = start + prose[start..].find(close)?;
+        Some((start, &prose[start..end]))
+    })
+    .max_by_key(|(start, _)| *start)
+    .map(|(_, answer)| decode_entities(&strip_tags(answer)).trim().to_string())
+    .filter(|answer| !answer.is_empty())
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut is_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => is_tag = true,
+            '>' if is_tag => is_tag = false,
+            _ if !is_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Manifest> for JsonValue {
+    fn from(value: Manifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "examples".into(),
+            JsonValue::Array(value.examples.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?;
+
+        Ok(Manifest {
+            examples: json_examples
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .filter(|part| *part == 1.0 || *part == 2.0)
+            .ok_or("Expected example.part to be 1 or 2.")?;
+
+        // NOTE: answers may be written as numbers for convenience.
+        let answer = match json.get("answer") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(JsonValue::String(s)) => Some(s.clone()),
+            Some(JsonValue::Number(n)) if n.fract() == 0.0 => Some(format!("{n:.0}")),
+            Some(_) => return Err("Expected example.answer to be null, string or integer.".into()),
+        };
+
+        Ok(Example {
+            file: file.clone(),
+            part: part as u8,
+            answer,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_examples, Example, Manifest, PuzzleExample};
+    use crate::day;
+
+    #[test]
+    fn parses_manifests() {
+        let json = r#"{ "examples": [
+            { "file": "08-1.txt", "part": 1, "answer": "6" },
+            { "file": "08-2.txt", "part": 2, "answer": 6 },
+            { "file": "08-2.txt", "part": 1, "answer": null }
+        ] }"#
+            .to_string();
+
+        let manifest = Manifest::try_from(json).unwrap();
+        assert_eq!(manifest.examples.len(), 3);
+        assert_eq!(manifest.examples[1].answer, Some("6".into()));
+        assert_eq!(manifest.part(1).count(), 2);
+        assert_eq!(
+            manifest.part(2).next(),
+            Some(&Example {
+                file: "08-2.txt".into(),
+                part: 2,
+                answer: Some("6".into())
+            })
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let manifest = Manifest::empty(day!(3));
+        let json = tinyjson::JsonValue::from(manifest.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Manifest::try_from(json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn extracts_examples_from_puzzles() {
+        let puzzle = r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a&lt;b<em>c</em>
+</code></pre>
+<p>Here, the values are <code>12</code> and <code>38</code>. Adding these together produces <code><em>142</em></code>.</p>
+<p><em>What is the sum?</em></p>
+</article>
+<p>Your puzzle answer was <code>54304</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wothree
+</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+</article>"#;
+
+        let examples = extract_examples(puzzle);
+        assert_eq!(
+            examples,
+            vec![
+                PuzzleExample {
+                    part: 1,
+                    input: Some("1abc2\npqr3stu8vwx\na<bc\n".into()),
+                    answer: Some("142".into()),
+                },
+                PuzzleExample {
+                    part: 2,
+                    input: Some("two1nine\neightwothree\n".into()),
+                    answer: Some("281".into()),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_emphasis_in_code_blocks() {
+        let puzzle = "<article><pre><code><em>1</em></code></pre><p>Nothing</p></article>";
+        let examples = extract_examples(puzzle);
+        assert_eq!(examples[0].answer, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_examples() {
+        let json = r#"{ "examples": [{ "file": "01.txt", "part": 3 }] }"#.to_string();
+        Manifest::try_from(json).unwrap();
+    }
+}
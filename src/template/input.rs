@@ -0,0 +1,147 @@
+/// Selection of the input a solution binary runs on.
+/// By default, solutions run on the puzzle input. The `--input` argument selects stdin, an arbitrary file or an example instead.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    str::FromStr,
+};
+
+use crate::template::{read_file, read_file_part, Day, Year};
+
+/// The source of the input a solution runs on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, e.g. `data/2023/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// The standard input, selected with `-`.
+    Stdin,
+    /// An example, selected with `example` (e.g. `01.txt`) or `example:N` (e.g. `01-N.txt`).
+    Example(Option<u8>),
+    /// Any other file, selected by its path.
+    File(String),
+}
+
+impl InputSource {
+    /// Reads the `--input <source>` argument of the current process, if present.
+    /// Exits the process if the argument is malformed.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let Some(value) = args
+            .iter()
+            .position(|x| x == "--input")
+            .and_then(|i| args.get(i + 1))
+        else {
+            return InputSource::default();
+        };
+
+        value.parse().unwrap_or_else(|e| {
+            eprintln!("Invalid input source `{value}`: {e}");
+            std::process::exit(1);
+        })
+    }
+
+    /// Reads the input of a day from this source. Panics if the input can't be read.
+    pub fn read(&self, year: Year, day: Day) -> String {
+        match self {
+            InputSource::Puzzle => read_file("inputs", year, day),
+            InputSource::Example(None) => read_file("examples", year, day),
+            InputSource::Example(Some(part)) => read_file_part("examples", year, day, *part),
+            InputSource::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read input from stdin");
+                input
+            }
+        }
+    }
+
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "input"),
+            InputSource::Stdin => write!(f, "-"),
+            InputSource::Example(None) => write!(f, "example"),
+            InputSource::Example(Some(part)) => write!(f, "example:{part}"),
+            InputSource::File(path) => write!(f, "{path}"),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("expected `-`, `example`, `example:<n>` or a file path.".into()),
+            "input" => Ok(InputSource::Puzzle),
+            "-" => Ok(InputSource::Stdin),
+            "example" => Ok(InputSource::Example(None)),
+            _ => match s.strip_prefix("example:") {
+                Some(n) => n
+                    .parse()
+                    .map(|n| InputSource::Example(Some(n)))
+                    .map_err(|_| format!("expected `example:<n>` with a number, got `{s}`.")),
+                None => Ok(InputSource::File(s.to_string())),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, str::FromStr};
+
+    use super::InputSource;
+    use crate::{day, year};
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(InputSource::from_str("-"), Ok(InputSource::Stdin));
+        assert_eq!(InputSource::from_str("input"), Ok(InputSource::Puzzle));
+        assert_eq!(
+            InputSource::from_str("example"),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            InputSource::from_str("example:2"),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert_eq!(
+            InputSource::from_str("data/big.txt"),
+            Ok(InputSource::File("data/big.txt".into()))
+        );
+        assert!(InputSource::from_str("example:x").is_err());
+        assert!(InputSource::from_str("").is_err());
+    }
+
+    #[test]
+    fn roundtrips_display() {
+        for s in ["-", "input", "example", "example:3", "./big.txt"] {
+            assert_eq!(InputSource::from_str(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn reads_files() {
+        let path = env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        fs::write(&path, "1 2 3").unwrap();
+
+        let source = InputSource::File(path.to_string_lossy().to_string());
+        assert_eq!(source.read(year!(2023), day!(1)), "1 2 3");
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,418 @@
+/// Module that keeps a local record of submitted answers.
+/// The ledger is used to refuse submissions that are already known to be wrong before they hit the website.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli::{Hint, SubmissionOutcome};
+use crate::template::{get_data_path, Day, Year};
+
+static LEDGER_FILE_NAME: &str = "submissions.json";
+
+/// The verdict advent of code returned for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    Rejected(Option<Hint>),
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// All submitted answers for one part of a day.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub day: Day,
+    pub part: u8,
+    pub attempts: Vec<Attempt>,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyAccepted(String),
+    KnownWrong,
+    TooHigh(String),
+    TooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyAccepted(answer) => {
+                write!(f, "this part was already solved with answer `{answer}`.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Refusal::TooHigh(bound) => {
+                write!(f, "answer is too high, `{bound}` was already too high.")
+            }
+            Refusal::TooLow(bound) => {
+                write!(f, "answer is too low, `{bound}` was already too low.")
+            }
+        }
+    }
+}
+
+/// Represents all answers submitted so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub data: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Dehydrate the ledger of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_data_path(year, LEDGER_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the ledger of a year from a JSON file. If not present, returns an empty ledger.
+    /// A file that can not be read is reported and treated as empty, so use [`Ledger::try_read_from_file`]
+    /// before storing the ledger, which would overwrite it.
+    pub fn read_from_file(year: Year) -> Self {
+        Self::try_read_from_file(year).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            Ledger::default()
+        })
+    }
+
+    /// Rehydrate the ledger of a year from a JSON file. If not present, returns an empty ledger.
+    /// Fails if the file exists but can not be read or parsed.
+    pub fn try_read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_data_path(year, LEDGER_FILE_NAME);
+
+        if !Path::new(&path).exists() {
+            return Ok(Ledger::default());
+        }
+
+        fs::read_to_string(&path)
+            .map_err(|x| x.to_string())
+            .and_then(Ledger::try_from)
+            .map_err(|e| format!("could not read \"{path}\": {e}"))
+    }
+
+    pub fn entry(&self, day: Day, part: u8) -> Option<&Entry> {
+        self.data.iter().find(|e| e.day == day && e.part == part)
+    }
+
+    /// Returns the accepted answer for a part, if any.
+    pub fn accepted(&self, day: Day, part: u8) -> Option<&str> {
+        self.entry(day, part)?
+            .attempts
+            .iter()
+            .find(|a| a.verdict == Verdict::Accepted)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Checks whether an answer is worth submitting.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let Some(entry) = self.entry(day, part) else {
+            return Ok(());
+        };
+
+        if let Some(accepted) = self.accepted(day, part) {
+            return Err(Refusal::AlreadyAccepted(accepted.to_string()));
+        }
+
+        if entry.attempts.iter().any(|a| a.answer == answer) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |hint: Hint| {
+            entry.attempts.iter().filter_map(move |a| {
+                if a.verdict == Verdict::Rejected(Some(hint)) {
+                    a.answer.trim().parse::<i128>().ok().map(|v| (v, &a.answer))
+                } else {
+                    None
+                }
+            })
+        };
+
+        if let Some((upper, answer)) = bound(Hint::TooHigh).min_by_key(|(v, _)| *v) {
+            if value >= upper {
+                return Err(Refusal::TooHigh(answer.clone()));
+            }
+        }
+
+        if let Some((lower, answer)) = bound(Hint::TooLow).max_by_key(|(v, _)| *v) {
+            if value <= lower {
+                return Err(Refusal::TooLow(answer.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the outcome of a submission.
+    /// Outcomes that do not carry a verdict (e.g. rate limiting) are not recorded.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, outcome: &SubmissionOutcome) {
+        let verdict = match outcome {
+            SubmissionOutcome::Correct => Verdict::Accepted,
+            SubmissionOutcome::Incorrect(hint) => Verdict::Rejected(*hint),
+            _ => return,
+        };
+
+        let attempt = Attempt {
+            answer: answer.to_string(),
+            verdict,
+        };
+
+        match self
+            .data
+            .iter_mut()
+            .find(|e| e.day == day && e.part == part)
+        {
+            Some(entry) => entry.attempts.push(attempt),
+            None => {
+                self.data.push(Entry {
+                    day,
+                    part,
+                    attempts: vec![attempt],
+                });
+                self.data.sort_unstable_by_key(|e| (e.day, e.part));
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Ledger> for JsonValue {
+    fn from(value: Ledger) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Ledger {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Ledger {
+            data: json_data
+                .iter()
+                .map(Entry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "attempts".into(),
+            JsonValue::Array(value.attempts.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected ledger entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .filter(|part| *part == 1.0 || *part == 2.0)
+            .ok_or("Expected entry.part to be 1 or 2.")?;
+
+        let attempts = json
+            .get("attempts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.attempts to be an array.")?
+            .iter()
+            .map(Attempt::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Entry {
+            day,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            part: part as u8,
+            attempts,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Attempt> for JsonValue {
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let verdict = match value.verdict {
+            Verdict::Accepted => "accepted",
+            Verdict::Rejected(None) => "wrong",
+            Verdict::Rejected(Some(Hint::TooHigh)) => "too_high",
+            Verdict::Rejected(Some(Hint::TooLow)) => "too_low",
+        };
+
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert("verdict".into(), JsonValue::String(verdict.into()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.answer to be a string.")?;
+
+        let verdict = match json.get("verdict").and_then(|v| v.get::<String>()) {
+            Some(v) if v == "accepted" => Verdict::Accepted,
+            Some(v) if v == "wrong" => Verdict::Rejected(None),
+            Some(v) if v == "too_high" => Verdict::Rejected(Some(Hint::TooHigh)),
+            Some(v) if v == "too_low" => Verdict::Rejected(Some(Hint::TooLow)),
+            _ => {
+                return Err(
+                    "Expected attempt.verdict to be one of accepted, wrong, too_high, too_low."
+                        .into(),
+                )
+            }
+        };
+
+        Ok(Attempt {
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Refusal};
+    use crate::{
+        day,
+        template::aoc_cli::{Hint, SubmissionOutcome},
+    };
+
+    fn get_mock_ledger() -> Ledger {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            day!(1),
+            1,
+            "100",
+            &SubmissionOutcome::Incorrect(Some(Hint::TooHigh)),
+        );
+        ledger.record(
+            day!(1),
+            1,
+            "10",
+            &SubmissionOutcome::Incorrect(Some(Hint::TooLow)),
+        );
+        ledger.record(day!(1), 1, "50", &SubmissionOutcome::Incorrect(None));
+        ledger.record(day!(2), 2, "abc", &SubmissionOutcome::Correct);
+        ledger.record(day!(3), 1, "1", &SubmissionOutcome::TooRecent);
+        ledger
+    }
+
+    #[test]
+    fn allows_new_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.check(day!(1), 1, "42"), Ok(()));
+        assert_eq!(ledger.check(day!(1), 2, "50"), Ok(()));
+        assert_eq!(ledger.check(day!(3), 1, "1"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.check(day!(1), 1, "50"), Err(Refusal::KnownWrong));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(day!(1), 1, "101"),
+            Err(Refusal::TooHigh("100".into()))
+        );
+        assert_eq!(
+            ledger.check(day!(1), 1, "-3"),
+            Err(Refusal::TooLow("10".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(day!(2), 2, "def"),
+            Err(Refusal::AlreadyAccepted("abc".into()))
+        );
+        assert_eq!(ledger.accepted(day!(2), 2), Some("abc"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let ledger = get_mock_ledger();
+        let json = tinyjson::JsonValue::from(ledger.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Ledger::try_from(json).unwrap();
+
+        assert_eq!(parsed.data.len(), 2);
+        assert_eq!(parsed.data[0].attempts, ledger.data[0].attempts);
+        assert_eq!(parsed.accepted(day!(2), 2), Some("abc"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_ledger() {
+        let json = r#"{ "data": [{ "day": "01", "part": 3, "attempts": [] }] }"#.to_string();
+        Ledger::try_from(json).unwrap();
+    }
+}
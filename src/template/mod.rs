@@ -2,14 +2,26 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
+mod compare;
 mod day;
-mod readme_benchmarks;
+mod ledger;
+mod module_template;
+mod readme;
+mod record;
 mod run_multi;
+mod stats;
+mod status;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,52 +29,165 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join("data")
+        .join(year.to_string())
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(year.to_string())
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Returns the path of a file in the data directory of a year, e.g. `data/2023/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(year: Year, path: &str) -> String {
+    format!("data/{year}/{path}")
+}
+
+/// Returns the name of the binary of a day, e.g. `2023_01`.
+#[must_use]
+pub fn get_bin_name(year: Year, day: Day) -> String {
+    format!("{year}_{day}")
+}
+
+#[must_use]
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(year, day))
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+/// The year is read from the name of the solution file, e.g. `src/bin/2023_01.rs`.
+/// Also creates the constant `SOLUTION`, which describes the day for the solution registry,
+/// and the function `solve_part`, which the tests of `example_tests!` run examples with.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional `parse = <function>` parameter sets a function that parses the input once.
+/// Its output is passed to both parts by reference, and parsing is timed separately from solving.
+///
+/// The optional, last parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Parts return either an `Option` of the answer or a `Result` with an error that implements `Display`,
+/// see [`runner::IntoAnswer`]. Errors and panics are reported per part, and do not stop the other parts.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, [$parse], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl $day, [$parse], [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl $day, [$parse], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
+        /// The year of the current day.
+        const YEAR: $crate::template::Year = $crate::template::Year::__from_bin_path(file!());
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// This day, as registered in the solution registry.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution::new(
+                YEAR,
+                DAY,
+                &[$({ let _ = stringify!($parse); $crate::template::runner::PARSE },)? $($part),*],
+                run_solution,
+            );
+
+        /// Runs this day in-process. Used by the solution registry.
+        #[allow(dead_code)]
+        fn run_solution(
+            input: &str,
+            bench: Option<$crate::template::runner::BenchConfig>,
+            on_result: &mut dyn FnMut($crate::template::runner::PartResult),
+        ) {
+            use $crate::template::runner::*;
+            $(
+                let (parsed, result) = execute_parse($parse, input, bench);
+                on_result(result);
+                let Some(parsed) = parsed else {
+                    return;
+                };
+                let input = &parsed;
+            )?
+            $( on_result(execute_part($func, input, $part, bench)); )*
+        }
+
+        /// Solves a single part and formats its answer, or the reason why there is none. Used by the example tests.
+        #[allow(dead_code)]
+        fn solve_part(input: &str, part: u8) -> Result<String, Option<String>> {
+            use $crate::template::runner::IntoAnswer;
+            $( let input = &$parse(input); )?
+            $(
+                if part == $part {
+                    return $func(input).into_answer();
+                }
+            )*
+            Err(None)
+        }
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::input::InputSource::from_args().read(YEAR, DAY);
+            let input = input.as_str();
+            write_parts(DAY, SOLUTION.parts);
+            $(
+                let Some(parsed) = run_parse($parse, input, DAY) else {
+                    return;
+                };
+                let input = &parsed;
+            )?
+            $( run_part($func, input, YEAR, DAY, $part); )*
+        }
+    };
+}
+
+/// Creates a test for each part, which checks the part against the examples listed in the manifest of the day,
+/// e.g. `data/2023/examples/01.json`. Requires `solution!` to be invoked in the same module.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        #[cfg(test)]
+        mod example_tests {
+            #[test]
+            fn part_one() {
+                $crate::template::examples::check(super::YEAR, super::DAY, 1, super::solve_part);
+            }
+
+            #[test]
+            fn part_two() {
+                $crate::template::examples::check(super::YEAR, super::DAY, 2, super::solve_part);
+            }
         }
     };
 }
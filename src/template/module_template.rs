@@ -0,0 +1,185 @@
+/// Module that renders the templates `cargo scaffold` creates solution files from.
+/// Templates are text files in `src/templates`, e.g. `src/templates/grid.txt`, and are chosen by name with `--template`.
+///
+/// A template may start with directives, which are not copied to the solution file:
+/// `%% examples: 2` creates a separate example file for each part.
+use std::{fmt::Display, fs, io};
+
+use crate::template::{Day, Year};
+
+pub const TEMPLATE_DIR: &str = "./src/templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound {
+        name: String,
+        available: Vec<String>,
+    },
+    InvalidDirective(String),
+    Io(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound { name, available } => write!(
+                f,
+                "template `{name}` not found in \"{TEMPLATE_DIR}\", available templates: {}",
+                available.join(", ")
+            ),
+            TemplateError::InvalidDirective(line) => {
+                write!(f, "invalid template directive `{line}`")
+            }
+            TemplateError::Io(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for TemplateError {
+    fn from(e: io::Error) -> Self {
+        TemplateError::Io(e)
+    }
+}
+
+/// The values that are substituted for the placeholders of a template.
+pub struct Placeholders {
+    pub year: Year,
+    pub day: Day,
+    /// The title of the puzzle, e.g. `Day 1: Trebuchet?!`.
+    pub title: Option<String>,
+    /// The example files of the day, e.g. `["08-1.txt", "08-2.txt"]`.
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ModuleTemplate {
+    pub name: String,
+    /// Number of example files to create: one that is shared by both parts, or one per part.
+    pub examples: u8,
+    body: String,
+}
+
+impl ModuleTemplate {
+    /// Reads a template from the template directory.
+    pub fn load(name: &str) -> Result<Self, TemplateError> {
+        match fs::read_to_string(format!("{TEMPLATE_DIR}/{name}.txt")) {
+            Ok(source) => Self::parse(name, &source),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(TemplateError::NotFound {
+                name: name.to_string(),
+                available: available()?,
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(name: &str, source: &str) -> Result<Self, TemplateError> {
+        let mut template = Self {
+            name: name.to_string(),
+            examples: 1,
+            body: String::new(),
+        };
+
+        let mut body = source;
+        while let Some(line) = body.strip_prefix("%%") {
+            let (line, rest) = line.split_once('\n').unwrap_or((line, ""));
+            body = rest;
+
+            match line.split_once(':').map(|(k, v)| (k.trim(), v.trim())) {
+                Some(("examples", "1")) => template.examples = 1,
+                Some(("examples", "2")) => template.examples = 2,
+                _ => return Err(TemplateError::InvalidDirective(format!("%%{line}"))),
+            }
+        }
+
+        template.body = body.to_string();
+        Ok(template)
+    }
+
+    /// Renders the template, replacing `%YEAR%`, `%DAY%` (e.g. `05`), `%DAY_NUMBER%` (e.g. `5`),
+    /// `%TITLE%` and `%EXAMPLES%`.
+    pub fn render(&self, placeholders: &Placeholders) -> String {
+        let day = placeholders.day;
+        let title = placeholders
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+        let examples = placeholders
+            .examples
+            .iter()
+            .map(|file| format!("`{file}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.body
+            .replace("%YEAR%", &placeholders.year.to_string())
+            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .replace("%DAY%", &day.to_string())
+            .replace("%TITLE%", &title)
+            .replace("%EXAMPLES%", &examples)
+    }
+}
+
+/// Returns the names of all templates in the template directory.
+pub fn available() -> Result<Vec<String>, io::Error> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATE_DIR)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_suffix(".txt")
+                .map(String::from)
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ModuleTemplate, Placeholders, TemplateError};
+    use crate::{day, year};
+
+    #[test]
+    fn renders_placeholders() {
+        let template = ModuleTemplate::parse(
+            "test",
+            "//! %YEAR% %TITLE% (%EXAMPLES%)\nsolution!(%DAY_NUMBER%); // %DAY%\n",
+        )
+        .unwrap();
+
+        let placeholders = Placeholders {
+            year: year!(2023),
+            day: day!(8),
+            title: Some("Day 8: Haunted Wasteland".into()),
+            examples: vec!["08-1.txt".into(), "08-2.txt".into()],
+        };
+        assert_eq!(
+            template.render(&placeholders),
+            "//! 2023 Day 8: Haunted Wasteland (`08-1.txt`, `08-2.txt`)\nsolution!(8); // 08\n"
+        );
+
+        let placeholders = Placeholders {
+            title: None,
+            ..placeholders
+        };
+        assert!(template
+            .render(&placeholders)
+            .starts_with("//! 2023 Day 8 ("));
+    }
+
+    #[test]
+    fn reads_directives() {
+        let template = ModuleTemplate::parse("test", "%% examples: 2\nsolution!(1);\n").unwrap();
+        assert_eq!(template.examples, 2);
+        assert_eq!(template.body, "solution!(1);\n");
+
+        assert!(matches!(
+            ModuleTemplate::parse("test", "%% examples: 3\n"),
+            Err(TemplateError::InvalidDirective(_))
+        ));
+    }
+}
@@ -0,0 +1,222 @@
+/// Module that generates the benchmark table of the readme from the stored timings.
+use crate::template::readme::Section;
+use crate::template::runner::PARSE;
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{get_path_for_bin, Year};
+
+pub static MARKER: &str = "<!--- benchmarking table --->";
+
+/// The benchmark table, with a row for every timed day.
+pub fn section(year: Year, timings: &Timings, total_millis: f64) -> Section {
+    // only show a parse column if at least one day uses a separate `parse` function.
+    let has_parse = timings
+        .data
+        .iter()
+        .any(|t| t.parse.is_some() || t.failure.as_ref().is_some_and(|f| f.part == PARSE));
+
+    let mut lines: Vec<String> = vec!["## Benchmarks".into(), String::new()];
+
+    if has_parse {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
+
+    for timing in &timings.data {
+        let path = get_path_for_bin(year, timing.day);
+        let parse = if has_parse {
+            format!(" `{}` |", part_cell(timing, PARSE))
+        } else {
+            String::new()
+        };
+
+        lines.push(format!(
+            "| [Day {}]({}) |{} `{}` | `{}` |",
+            timing.day.into_inner(),
+            path,
+            parse,
+            part_cell(timing, 1),
+            part_cell(timing, 2)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    Section {
+        marker: MARKER,
+        content: lines.join("\n"),
+    }
+}
+
+/// Show the duration of a part, or how it failed.
+fn part_cell(timing: &Timing, part: u8) -> String {
+    if let Some(failure) = timing.failure.as_ref().filter(|f| f.part == part) {
+        return failure.failure.summary();
+    }
+
+    match part {
+        PARSE => format_cell(timing.parse.clone(), timing.parse_stats.as_ref()),
+        1 => format_cell(timing.part_1.clone(), timing.part_1_stats.as_ref()),
+        _ => format_cell(timing.part_2.clone(), timing.part_2_stats.as_ref()),
+    }
+}
+
+/// Show the spread next to the median when statistics are available.
+fn format_cell(duration: Option<String>, stats: Option<&Stats>) -> String {
+    match (duration, stats) {
+        (Some(duration), Some(stats)) if stats.samples > 1 => {
+            format!("{duration} ± {:.1?}", stats.stddev)
+        }
+        (Some(duration), _) => duration,
+        (None, _) => "-".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{section, MARKER};
+    use crate::template::readme;
+    use crate::template::runner::Failure;
+    use crate::template::timings::{DayFailure, Timing, Timings};
+    use crate::template::Year;
+    use crate::{day, year};
+
+    fn update_content(
+        s: &mut String,
+        year: Year,
+        timings: Timings,
+        total_millis: f64,
+    ) -> Result<(), readme::Error> {
+        readme::update_content(s, &[section(year, &timings, total_millis)])
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
+                    total_nanos: 3e+10,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("30ms".into()),
+                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
+                    total_nanos: 7e+10,
+                },
+                Timing {
+                    day: day!(4),
+                    part_1: Some("40ms".into()),
+                    part_2: Some("50ms".into()),
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
+                    total_nanos: 9e+10,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_too_many_markers_present() {
+        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+    }
+
+    #[test]
+    fn updates_empty_benchmarks() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## Benchmarks"), true);
+    }
+
+    #[test]
+    fn updates_existing_benchmarks() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+    }
+
+    #[test]
+    fn format_benchmarks() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023_04.rs) | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 195.0).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023_01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023_02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_failures() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].failure = Some(DayFailure {
+            part: 2,
+            failure: Failure::Timeout(Duration::from_secs(60)),
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, year!(2023), timings, 100.0).unwrap();
+
+        assert!(s.contains("| [Day 4](./src/bin/2023_04.rs) | `40ms` | `timed out after 60s` |"));
+    }
+}
@@ -0,0 +1,257 @@
+/// Module that generates the sections of the readme: the progress table and the benchmark table.
+/// Every section sits between two copies of its marker, e.g. `<!--- benchmarking table --->`.
+/// A single marker, as in a fresh readme, is replaced with the section and two markers around it.
+/// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::{fmt::Display, fs, io};
+
+use crate::template::answers::Answers;
+use crate::template::ledger::Ledger;
+use crate::template::timings::Timings;
+use crate::template::Year;
+
+pub mod benchmarks;
+pub mod stars;
+
+static README_PATH: &str = "README.md";
+
+/// A generated part of the readme.
+pub struct Section {
+    pub marker: &'static str,
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MarkerErrorKind {
+    Missing,
+    /// The marker occurs more than twice, on the given lines.
+    TooMany(Vec<usize>),
+    /// The section overlaps with the section of another marker.
+    Overlaps(&'static str),
+}
+
+/// A marker that could not be located in the readme.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MarkerError {
+    pub marker: &'static str,
+    pub kind: MarkerErrorKind,
+}
+
+impl Display for MarkerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let marker = self.marker;
+        match &self.kind {
+            MarkerErrorKind::Missing => write!(
+                f,
+                "marker `{marker}` not found. Add it on a line of its own where the table should go."
+            ),
+            MarkerErrorKind::TooMany(lines) => write!(
+                f,
+                "marker `{marker}` occurs {} times, on lines {}. Keep one before the first update, or two around the generated table.",
+                lines.len(),
+                lines
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            MarkerErrorKind::Overlaps(other) => write!(
+                f,
+                "the section of marker `{marker}` overlaps with the section of marker `{other}`. Move one of them."
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Markers(Vec<MarkerError>),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Markers(errors) => {
+                write!(
+                    f,
+                    "could not locate the generated sections of {README_PATH}:"
+                )?;
+                for error in errors {
+                    write!(f, "\n  - {error}")?;
+                }
+                Ok(())
+            }
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// The byte range of a section in the readme, including its markers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SectionPosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+fn line_of(readme: &str, pos: usize) -> usize {
+    readme[..pos].matches('\n').count() + 1
+}
+
+fn locate_section(readme: &str, marker: &'static str) -> Result<SectionPosition, MarkerError> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    let error = |kind| MarkerError { marker, kind };
+
+    match matches.as_slice() {
+        [] => Err(error(MarkerErrorKind::Missing)),
+        [first] | [first, _] => Ok(SectionPosition {
+            pos_start: first.0,
+            pos_end: matches.last().map(|m| m.0 + m.1.len()).unwrap(),
+        }),
+        _ => Err(error(MarkerErrorKind::TooMany(
+            matches.iter().map(|m| line_of(readme, m.0)).collect(),
+        ))),
+    }
+}
+
+/// Replaces every section in `readme`. Nothing is changed if any of the markers can not be located.
+pub fn update_content(readme: &mut String, sections: &[Section]) -> Result<(), Error> {
+    let mut errors = vec![];
+    let mut positions = vec![];
+
+    for section in sections {
+        match locate_section(readme, section.marker) {
+            Ok(position) => positions.push((position, section)),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    positions.sort_unstable_by_key(|(position, _)| position.pos_start);
+
+    for pair in positions.windows(2) {
+        let ((previous, previous_section), (position, section)) = (&pair[0], &pair[1]);
+        if position.pos_start < previous.pos_end {
+            errors.push(MarkerError {
+                marker: section.marker,
+                kind: MarkerErrorKind::Overlaps(previous_section.marker),
+            });
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(Error::Markers(errors));
+    }
+
+    // replace from the end, so the positions of earlier sections stay valid.
+    for (position, section) in positions.iter().rev() {
+        let content = [section.marker, &section.content, section.marker].join("\n");
+        readme.replace_range(position.pos_start..position.pos_end, &content);
+    }
+
+    Ok(())
+}
+
+/// Regenerates the progress and benchmark tables of the readme.
+pub fn update(year: Year, timings: &Timings) -> Result<(), Error> {
+    let answers = Answers::read_from_file(year);
+    let ledger = Ledger::read_from_file(year);
+
+    let sections = [
+        stars::section(year, &stars::read_progress(year, &answers, &ledger)),
+        benchmarks::section(year, timings, timings.total_millis()),
+    ];
+
+    let mut readme = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
+    update_content(&mut readme, &sections)?;
+    fs::write(README_PATH, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Error, MarkerError, MarkerErrorKind, Section};
+
+    const FIRST: &str = "<!--- first --->";
+    const SECOND: &str = "<!--- second --->";
+
+    fn sections() -> [Section; 2] {
+        [
+            Section {
+                marker: FIRST,
+                content: "first table".into(),
+            },
+            Section {
+                marker: SECOND,
+                content: "second table".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn updates_all_sections() {
+        let mut s = format!("# readme\n{FIRST}\n\n{SECOND}\n{SECOND}\nend");
+        update_content(&mut s, &sections()).unwrap();
+        update_content(&mut s, &sections()).unwrap();
+
+        assert_eq!(
+            s,
+            format!(
+                "# readme\n{FIRST}\nfirst table\n{FIRST}\n\n{SECOND}\nsecond table\n{SECOND}\nend"
+            )
+        );
+    }
+
+    #[test]
+    fn reports_all_marker_errors() {
+        let readme = format!("{SECOND}\n\n{SECOND}\n{SECOND}\n");
+        let mut s = readme.clone();
+
+        let Err(Error::Markers(errors)) = update_content(&mut s, &sections()) else {
+            panic!("expected marker errors");
+        };
+
+        assert_eq!(
+            errors,
+            [
+                MarkerError {
+                    marker: FIRST,
+                    kind: MarkerErrorKind::Missing,
+                },
+                MarkerError {
+                    marker: SECOND,
+                    kind: MarkerErrorKind::TooMany(vec![1, 3, 4]),
+                }
+            ]
+        );
+        assert_eq!(
+            errors[1].to_string(),
+            "marker `<!--- second --->` occurs 3 times, on lines 1, 3, 4. Keep one before the first update, or two around the generated table."
+        );
+        assert_eq!(s, readme);
+    }
+
+    #[test]
+    fn reports_overlapping_sections() {
+        let mut s = format!("{FIRST}\n{SECOND}\n{FIRST}\n{SECOND}");
+
+        let Err(Error::Markers(errors)) = update_content(&mut s, &sections()) else {
+            panic!("expected marker errors");
+        };
+
+        assert_eq!(
+            errors,
+            [MarkerError {
+                marker: SECOND,
+                kind: MarkerErrorKind::Overlaps(FIRST),
+            }]
+        );
+    }
+}
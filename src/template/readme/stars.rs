@@ -0,0 +1,162 @@
+/// Module that generates the progress table of the readme from the local answers.
+/// A part earns a star once advent of code accepted its answer. Answers that are only recorded in `answers.json`
+/// are shown with a hollow star and do not count towards the total.
+use std::{fs, path::Path};
+
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::{extract_title, get_puzzle_path};
+use crate::template::ledger::Ledger;
+use crate::template::readme::Section;
+use crate::template::status::PartStatus;
+use crate::template::{all_days, get_bin_name, get_path_for_bin, Day, Year};
+
+pub static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The progress of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    /// The title of the puzzle, e.g. `Day 1: Trebuchet?!`, if its description was downloaded.
+    pub title: Option<String>,
+    pub parts: [PartStatus; 2],
+    pub is_scaffolded: bool,
+}
+
+/// Reads the progress of every day that was scaffolded or has an answer.
+pub fn read_progress(year: Year, answers: &Answers, ledger: &Ledger) -> Vec<DayProgress> {
+    all_days()
+        .map(|day| DayProgress {
+            day,
+            title: fs::read_to_string(get_puzzle_path(year, day))
+                .ok()
+                .and_then(|puzzle| extract_title(&puzzle)),
+            parts: [1, 2].map(|part| PartStatus::of(day, part, answers, ledger)),
+            is_scaffolded: Path::new(&get_path_for_bin(year, day)).exists(),
+        })
+        .filter(|progress| {
+            progress.is_scaffolded
+                || progress
+                    .parts
+                    .iter()
+                    .any(|status| *status != PartStatus::Unsolved)
+        })
+        .collect()
+}
+
+/// The progress table, with a row for every day in `progress`.
+pub fn section(year: Year, progress: &[DayProgress]) -> Section {
+    let mut lines: Vec<String> = vec![
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 | Solution |".into(),
+        "| :--- | :---: | :---: | :---: |".into(),
+    ];
+
+    for day in progress {
+        let title = day
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Day {}", day.day.into_inner()))
+            .replace('|', "\\|");
+
+        let solution = if day.is_scaffolded {
+            format!(
+                "[{}.rs]({})",
+                get_bin_name(year, day.day),
+                get_path_for_bin(year, day.day)
+            )
+        } else {
+            "-".into()
+        };
+
+        let [part_1, part_2] = day.parts.map(|status| match status {
+            PartStatus::Accepted => "⭐",
+            PartStatus::Answered => "☆",
+            PartStatus::Unsolved => "",
+        });
+
+        lines.push(format!(
+            "| [{title}](https://adventofcode.com/{year}/day/{}) | {part_1} | {part_2} | {solution} |",
+            day.day.into_inner()
+        ));
+    }
+
+    let count = |status: PartStatus| {
+        progress
+            .iter()
+            .flat_map(|day| day.parts)
+            .filter(|s| *s == status)
+            .count()
+    };
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {}⭐**", count(PartStatus::Accepted)));
+
+    if count(PartStatus::Answered) > 0 {
+        lines.push(String::new());
+        lines.push("☆ answer recorded in `answers.json`, but not accepted via `--submit`".into());
+    }
+
+    Section {
+        marker: MARKER,
+        content: lines.join("\n"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{section, DayProgress, MARKER};
+    use crate::template::readme::update_content;
+    use crate::template::status::PartStatus;
+    use crate::{day, year};
+
+    #[test]
+    fn format_progress() {
+        let progress = [
+            DayProgress {
+                day: day!(1),
+                title: Some("Day 1: Trebuchet?!".into()),
+                parts: [PartStatus::Accepted, PartStatus::Accepted],
+                is_scaffolded: true,
+            },
+            DayProgress {
+                day: day!(2),
+                title: None,
+                parts: [PartStatus::Accepted, PartStatus::Answered],
+                is_scaffolded: true,
+            },
+            DayProgress {
+                day: day!(3),
+                title: Some("Day 3: A | B".into()),
+                parts: [PartStatus::Unsolved, PartStatus::Unsolved],
+                is_scaffolded: false,
+            },
+        ];
+
+        let mut s = format!("# readme\n{MARKER}\n\nfooter");
+        update_content(&mut s, &[section(year!(2023), &progress)]).unwrap();
+
+        let expected = [
+            "# readme",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 | Solution |",
+            "| :--- | :---: | :---: | :---: |",
+            "| [Day 1: Trebuchet?!](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ | [2023_01.rs](./src/bin/2023_01.rs) |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ | ☆ | [2023_02.rs](./src/bin/2023_02.rs) |",
+            "| [Day 3: A \\| B](https://adventofcode.com/2023/day/3) |  |  | - |",
+            "",
+            "**Total: 3⭐**",
+            "",
+            "☆ answer recorded in `answers.json`, but not accepted via `--submit`",
+            "<!--- advent_readme_stars table --->",
+            "",
+            "footer",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,323 @@
+/// Machine-readable records of solution runs.
+/// When `AOC_RECORD_FILE` is set, the runner first announces the parts of the day and then appends one JSON line per part to that file.
+/// This is used to collect answers and timings from child processes without parsing their output.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::{Failure, PartResult};
+use crate::template::Day;
+
+/// Environment variable holding the path of the record file.
+pub static RECORD_FILE_ENV: &str = "AOC_RECORD_FILE";
+
+/// The record of running a single part of a day. Part `0` records the day's `parse` function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub failure: Option<Failure>,
+    pub mean_nanos: f64,
+    pub samples: u128,
+    pub sample_nanos: Vec<f64>,
+}
+
+impl PartRecord {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_result(day: Day, result: &PartResult) -> Self {
+        Self {
+            day,
+            part: result.part,
+            answer: result.answer.clone(),
+            failure: result.failure.clone(),
+            mean_nanos: result.stats.mean.as_nanos() as f64,
+            samples: result.sample_durations.len() as u128,
+            sample_nanos: result
+                .sample_durations
+                .iter()
+                .map(|d| d.as_nanos() as f64)
+                .collect(),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn into_result(self) -> PartResult {
+        PartResult::new(
+            self.part,
+            self.answer,
+            self.sample_nanos
+                .iter()
+                .map(|nanos| Duration::from_nanos(*nanos as u64))
+                .collect(),
+        )
+        .with_failure(self.failure)
+    }
+
+    /// Append the record as a single JSON line to the file at `path`.
+    pub fn append_to(&self, path: &str) -> Result<(), io::Error> {
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")
+    }
+
+    /// Read all records from a JSON lines file, skipping the announcement of the parts.
+    pub fn read_all(path: &str) -> Result<Vec<Self>, String> {
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())?
+            .lines()
+            .filter(|line| !line.trim().is_empty() && parse_parts(line).is_none())
+            .map(PartRecord::from_str)
+            .collect()
+    }
+}
+
+/// Append the parts a day is about to run, in order, as a single JSON line to the file at `path`.
+/// This tells the reader whether a missing part `0` means that the `parse` function never finished.
+pub fn append_parts(path: &str, day: Day, parts: &[u8]) -> Result<(), io::Error> {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("day".into(), JsonValue::String(day.to_string()));
+    map.insert(
+        "parts".into(),
+        JsonValue::Array(
+            parts
+                .iter()
+                .map(|part| JsonValue::Number(f64::from(*part)))
+                .collect(),
+        ),
+    );
+
+    let line = JsonValue::Object(map)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Read the parts announced in a JSON lines file, if the runner announced them.
+pub fn read_parts(path: &str) -> Option<Vec<u8>> {
+    fs::read_to_string(path).ok()?.lines().find_map(parse_parts)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_parts(line: &str) -> Option<Vec<u8>> {
+    JsonValue::from_str(line)
+        .ok()?
+        .get::<HashMap<String, JsonValue>>()?
+        .get("parts")?
+        .get::<Vec<JsonValue>>()?
+        .iter()
+        .map(|v| v.get::<f64>().map(|part| *part as u8))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "failure".into(),
+            match &value.failure {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "sample_nanos".into(),
+            JsonValue::Array(
+                value
+                    .sample_nanos
+                    .iter()
+                    .map(|n| JsonValue::Number(*n))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for PartRecord {
+    type Err = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("record is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .filter(|part| [0.0, 1.0, 2.0].contains(part))
+            .ok_or("Expected record.part to be 0 (parse), 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        // records of older runners have no failure.
+        let failure = match json.get("failure") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Failure::try_from(v)?),
+        };
+
+        let mean_nanos = json
+            .get("mean_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.mean_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let sample_nanos = json
+            .get("sample_nanos")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected record.sample_nanos to be an array.")?
+            .iter()
+            .map(|v| v.get::<f64>().copied())
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Expected record.sample_nanos to contain numbers.")?;
+
+        Ok(PartRecord {
+            day,
+            part: part as u8,
+            answer: answer.cloned(),
+            failure,
+            mean_nanos,
+            samples: samples as u128,
+            sample_nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use super::{append_parts, read_parts, PartRecord};
+    use crate::day;
+    use crate::template::runner::Failure;
+
+    fn get_mock_record() -> PartRecord {
+        PartRecord {
+            day: day!(5),
+            part: 2,
+            answer: Some("46".into()),
+            failure: None,
+            mean_nanos: 1500.0,
+            samples: 2,
+            sample_nanos: vec![1000.0, 2000.0],
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = get_mock_record();
+        let line = tinyjson::JsonValue::from(&record).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartRecord::from_str(&line).unwrap(), record);
+    }
+
+    #[test]
+    fn converts_to_results() {
+        let result = get_mock_record().into_result();
+        assert_eq!(result.part, 2);
+        assert_eq!(result.answer, Some("46".into()));
+        assert_eq!(result.stats.mean.as_nanos(), 1500);
+        assert_eq!(result.sample_durations.len(), 2);
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let record = PartRecord::from_str(
+            r#"{ "day": "01", "part": 1, "answer": null, "mean_nanos": 0, "samples": 1, "sample_nanos": [0] }"#,
+        )
+        .unwrap();
+        assert_eq!(record.answer, None);
+        assert_eq!(record.failure, None);
+    }
+
+    #[test]
+    fn keeps_failures() {
+        for failure in [
+            Failure::Error("unknown color `purple` at line 1, column 12".into()),
+            Failure::Crash("no entry found for key".into()),
+            Failure::Timeout(Duration::from_secs(10)),
+        ] {
+            let record = PartRecord {
+                answer: None,
+                failure: Some(failure),
+                ..get_mock_record()
+            };
+            let line = tinyjson::JsonValue::from(&record).stringify().unwrap();
+            let result = PartRecord::from_str(&line).unwrap().into_result();
+            assert_eq!(result.answer, None);
+            assert_eq!(result.failure, record.failure);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        PartRecord::from_str(r#"{ "day": "01", "part": 1 }"#).unwrap();
+    }
+
+    #[test]
+    fn separates_parts_from_records() {
+        let path =
+            std::env::temp_dir().join(format!("aoc-test-parts-{}.jsonl", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(read_parts(&path), None);
+
+        append_parts(&path, day!(5), &[0, 1, 2]).unwrap();
+        get_mock_record().append_to(&path).unwrap();
+
+        assert_eq!(read_parts(&path), Some(vec![0, 1, 2]));
+        assert_eq!(
+            PartRecord::read_all(&path).unwrap(),
+            vec![get_mock_record()]
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+/// Library-level registry of solutions.
+/// The `solution!` macro describes each day as a [`Solution`]. With the `in-process` feature, the main binary
+/// compiles all days in `src/bin` into itself and registers them on startup, so that days can be run without
+/// spawning one cargo process per day. Without it, the registry is empty and days run as child processes.
+use std::sync::OnceLock;
+
+use crate::template::runner::{BenchConfig, PartResult};
+use crate::template::{Day, Year};
+
+/// Runs a day against an input, benchmarking it if a [`BenchConfig`] is passed.
+/// The result of every part is passed to the callback as soon as the part has finished.
+/// Generated by the `solution!` macro, so the parsed input of a day never has to be type-erased.
+pub type Run = fn(&str, Option<BenchConfig>, &mut dyn FnMut(PartResult));
+
+/// A single day's solution.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// The parts of the day in the order they run, starting with [`PARSE`](crate::template::runner::PARSE)
+    /// if the day has a `parse` function.
+    pub parts: &'static [u8],
+    pub run: Run,
+}
+
+impl Solution {
+    pub const fn new(year: Year, day: Day, parts: &'static [u8], run: Run) -> Self {
+        Self {
+            year,
+            day,
+            parts,
+            run,
+        }
+    }
+}
+
+static REGISTRY: OnceLock<Vec<Solution>> = OnceLock::new();
+
+/// Registers the solutions that are compiled into the current binary.
+/// Only the first call has an effect.
+pub fn register(solutions: impl IntoIterator<Item = Solution>) {
+    let mut solutions: Vec<Solution> = solutions.into_iter().collect();
+    solutions.sort_unstable_by_key(|s| (s.year, s.day));
+    let _ = REGISTRY.set(solutions);
+}
+
+/// Returns all registered solutions, sorted by year and day.
+pub fn solutions() -> &'static [Solution] {
+    REGISTRY.get().map_or(&[], Vec::as_slice)
+}
+
+/// Returns the registered solutions of a year, sorted by day.
+pub fn solutions_for(year: Year) -> impl Iterator<Item = &'static Solution> {
+    solutions().iter().filter(move |s| s.year == year)
+}
+
+/// Returns the registered solution for a day, if any.
+pub fn get(year: Year, day: Day) -> Option<&'static Solution> {
+    solutions_for(year).find(|s| s.day == day)
+}
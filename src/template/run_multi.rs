@@ -1,16 +1,90 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    env,
+    fmt::Display,
+    fs, io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{self, BenchConfig, PartResult, PARSE};
+use crate::template::{get_data_path, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{DayFailure, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Environment variable holding the default time limit of a part in seconds.
+pub static PART_TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+/// Environment variable holding the default time limit of a day in seconds.
+pub static DAY_TIMEOUT_ENV: &str = "AOC_DAY_TIMEOUT";
+
+/// Time limits of the days in a multi-day run.
+#[derive(Clone, Copy, Debug)]
+pub struct Timeouts {
+    /// The time a single part may take, including its benchmark.
+    pub part: Duration,
+    /// The time all parts of a day may take together.
+    pub day: Duration,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            part: Duration::from_secs(60),
+            day: Duration::from_secs(120),
+        }
+    }
+}
+
+impl Timeouts {
+    /// Creates timeouts from the command line arguments in seconds, falling back to
+    /// [`PART_TIMEOUT_ENV`] and [`DAY_TIMEOUT_ENV`], and then to the [`Default`].
+    pub fn new(part: Option<f64>, day: Option<f64>) -> Self {
+        let seconds = |arg: Option<f64>, key: &str| {
+            arg.or_else(|| env::var(key).ok()?.parse().ok())
+                .filter(|seconds: &f64| seconds.is_finite() && *seconds > 0.0)
+                .map(Duration::from_secs_f64)
+        };
+
+        let default = Self::default();
+        Self {
+            part: seconds(part, PART_TIMEOUT_ENV).unwrap_or(default.part),
+            day: seconds(day, DAY_TIMEOUT_ENV).unwrap_or(default.day),
+        }
+    }
+}
+
+/// Run a set of days of a year and collect their timings if a [`BenchConfig`] is passed.
+///
+/// By default, days are run in-process through the solution [`registry`].
+/// With `is_isolated`, or if the registry is empty because the `in-process` feature is disabled,
+/// every day is run as a separate child process instead.
+/// Parts that exceed the [`Timeouts`] or crash are reported, and the run continues with the next day.
+/// Once an in-process day timed out, its abandoned worker competes for the CPU, so the timings of later days
+/// are not recorded.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<BenchConfig>,
+    is_isolated: bool,
+    timeouts: Timeouts,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
+    let is_isolated = is_isolated || registry::solutions().is_empty();
+
+    let mut abandoned: Option<Day> = None;
+    let mut skipped: Vec<Day> = vec![];
 
     // NOTE: use non-duplicate, sorted day values.
     all_days()
@@ -24,17 +98,33 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            if !is_isolated {
+                if let Some(results) = run_in_process(year, day, bench, timeouts) {
+                    if abandoned.is_some() {
+                        skipped.push(day);
+                    } else if is_abandoned(&results) {
+                        abandoned = Some(day);
+                    }
+                    timings.push(timing_from_results(day, &results));
+                }
+                return;
+            }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            match child_commands::run_solution(year, day, bench, is_release, timeouts) {
+                Ok(results) if results.is_empty() => println!("Not solved."),
+                Ok(results) => timings.push(timing_from_results(day, &results)),
+                Err(e) => eprintln!("Failed to run solution: {e}"),
             }
         });
 
-    if is_timed {
+    print_failures(&timings);
+
+    if bench.is_some() {
+        if let Some(day) = abandoned {
+            print_skipped(day, &skipped);
+            timings.retain(|timing| !skipped.contains(&timing.day));
+        }
+
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -46,211 +136,526 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// The stack size of the threads that run days in-process. Matches the main thread on most platforms.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Run all parts of a registered day in the current process, on a worker thread.
+/// Returns `None` if the day is not registered or its input is missing.
+pub fn run_in_process(
+    year: Year,
+    day: Day,
+    bench: Option<BenchConfig>,
+    timeouts: Timeouts,
+) -> Option<Vec<PartResult>> {
+    let Some(solution) = registry::get(year, day) else {
+        println!("Not solved.");
+        return None;
+    };
+
+    let Ok(input) = fs::read_to_string(get_data_path(year, &format!("inputs/{day}.txt"))) else {
+        println!("Missing input.");
+        return None;
+    };
+
+    run_on_worker(solution, input, bench, timeouts)
+}
+
+/// Run all parts of a solution against an input on a worker thread.
+/// Returns `None` if the worker could not be started.
+///
+/// A part that exceeds the [`Timeouts`] is reported as timed out. Threads cannot be killed, so the worker
+/// is abandoned instead: it stops printing and benchmarking, and skips its remaining parts.
+fn run_on_worker(
+    solution: &Solution,
+    input: String,
+    bench: Option<BenchConfig>,
+    timeouts: Timeouts,
+) -> Option<Vec<PartResult>> {
+    let day = solution.day;
+    let (sender, receiver) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    let run = solution.run;
+    let flag = Arc::clone(&cancelled);
+    let worker = thread::Builder::new()
+        .name(format!("day {day}"))
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            runner::cancel_with(flag);
+            run(&input, bench, &mut |result| {
+                let _ = sender.send(result);
+            });
+        });
+
+    if let Err(e) = worker {
+        eprintln!("Failed to start worker thread: {e}");
+        return None;
+    }
+
+    let mut results = vec![];
+    let day_deadline = Instant::now() + timeouts.day;
+
+    for &part in solution.parts {
+        let limit = timeouts
+            .part
+            .min(day_deadline.saturating_duration_since(Instant::now()));
+
+        match receiver.recv_timeout(limit) {
+            Ok(result) => results.push(result),
+            Err(RecvTimeoutError::Timeout) => {
+                cancelled.store(true, Ordering::Relaxed);
+                results.push(PartResult::failed(
+                    part,
+                    runner::Failure::Timeout(limit),
+                    limit,
+                ));
+                break;
+            }
+            // the day stopped early, e.g. because its `parse` function panicked.
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Some(results)
+}
+
+/// Whether an in-process run timed out and left its worker thread running in the background.
+pub fn is_abandoned(results: &[PartResult]) -> bool {
+    results
+        .iter()
+        .any(|r| matches!(r.failure, Some(runner::Failure::Timeout(_))))
+}
+
+/// Print the days whose timings were not recorded because an abandoned worker was still running.
+pub fn print_skipped(abandoned: Day, skipped: &[Day]) {
+    if skipped.is_empty() {
+        return;
+    }
+
+    let days = skipped
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let days = match skipped {
+        [_] => format!("day {days} is"),
+        _ => format!("days {days} are"),
+    };
+    println!(
+        "\n{ANSI_BOLD}Not recorded:{ANSI_RESET} day {abandoned} timed out and kept running in the background, \
+        so the timing of {days} skewed. Use `--isolated` for accurate timings."
+    );
+}
+
+/// Print the days whose run stopped early, if any.
+fn print_failures(timings: &[Timing]) {
+    let failures: Vec<(Day, &DayFailure)> = timings
+        .iter()
+        .filter_map(|t| Some((t.day, t.failure.as_ref()?)))
+        .collect();
+
+    if failures.is_empty() {
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Failures:{ANSI_RESET}");
+    for (day, failure) in failures {
+        println!(
+            "Day {day}, {}: {}",
+            runner::part_label(failure.part),
+            failure.failure
+        );
+    }
+}
+
+/// Convert the results of an in-process run to a [`Timing`].
+pub fn timing_from_results(day: Day, results: &[PartResult]) -> Timing {
+    let mut timing = Timing {
+        day,
+        part_1: None,
+        part_2: None,
+        parse: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        parse_stats: None,
+        failure: results.iter().find_map(|r| {
+            Some(DayFailure {
+                part: r.part,
+                failure: r.failure.clone()?,
+            })
+        }),
+        total_nanos: 0_f64,
+    };
+
+    for result in results
+        .iter()
+        .filter(|r| r.part == PARSE || r.answer.is_some())
+    {
+        let duration = Some(format!("{:.1?}", result.duration));
+        let stats = Some(result.stats.clone());
+
+        match result.part {
+            PARSE => (timing.parse, timing.parse_stats) = (duration, stats),
+            1 => (timing.part_1, timing.part_1_stats) = (duration, stats),
+            2 => (timing.part_2, timing.part_2_stats) = (duration, stats),
+            _ => {}
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            timing.total_nanos += result.duration.as_nanos() as f64;
+        }
+    }
+
+    timing
+}
+
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
+    BadExitStatus(Option<i32>),
+    MissingInput,
+    Record(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BadExitStatus(Some(code)) => {
+                write!(f, "child process exited with status {code}.")
+            }
+            Error::BadExitStatus(None) => write!(f, "child process was terminated."),
+            Error::MissingInput => write!(f, "missing input."),
+            Error::Record(e) => write!(f, "could not read run record: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their run records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, Timeouts};
+    use crate::template::record::{self, PartRecord, RECORD_FILE_ENV};
+    use crate::template::runner::{BenchConfig, Failure, PartResult};
+    use crate::template::{get_bin_name, get_data_path, get_path_for_bin, registry, Day, Year};
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        env, fs,
+        path::{Path, PathBuf},
+        process::{self, Command, ExitStatus, Output, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// How often a running child process is checked for progress.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Run the solution bin for a given day and return the results of its parts.
+    /// The bin is built with cargo first and then started directly, so the child process that is killed
+    /// when a part exceeds the [`Timeouts`] is the solution itself rather than a cargo wrapper.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        bench: Option<BenchConfig>,
+        is_release: bool,
+        timeouts: Timeouts,
+    ) -> Result<Vec<PartResult>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if !Path::new(&get_data_path(year, &format!("inputs/{day}.txt"))).exists() {
+            return Err(Error::MissingInput);
+        }
+
+        let bin_name = get_bin_name(year, day);
+        let mut build_args: Vec<&str> = vec!["build", "--quiet", "--bin", &bin_name];
 
         if is_release {
-            args.push("--release");
+            build_args.push("--release");
+        }
+
+        // build first, so compile times do not count towards the timeouts.
+        let status = Command::new("cargo")
+            .args(&build_args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        if !status.success() {
+            return Err(Error::BadExitStatus(status.code()));
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let mut args: Vec<String> = vec![];
+
+        if let Some(config) = bench {
+            // mirror `--time` and `--budget` flags to child invocations.
+            args.push("--time".into());
+            args.push("--budget".into());
+            args.push(config.budget.as_millis().to_string());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // the child appends a JSON record per part to this file.
+        let record_path = env::temp_dir().join(format!("aoc-{}-{year}-{day}.jsonl", process::id()));
+        let record_path = record_path.to_string_lossy().to_string();
+        let _ = fs::remove_file(&record_path);
 
-        let mut cmd = Command::new("cargo")
+        let mut child = Command::new(get_bin_path(&bin_name, is_release))
             .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .env(RECORD_FILE_ENV, &record_path)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let day_deadline = Instant::now() + timeouts.day;
+        let mut part_start = Instant::now();
+        let mut finished = 0;
 
-        let mut output = vec![];
+        // the child appends a record when it starts and whenever a part finishes, which starts the clock of the next part.
+        let (status, limit) = loop {
+            let limit = timeouts
+                .part
+                .min(day_deadline.saturating_duration_since(part_start));
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+            if let Some(status) = child.try_wait()? {
+                break (Some(status), limit);
+            }
+
+            let count = count_records(&record_path);
+            if count > finished {
+                finished = count;
+                part_start = Instant::now();
+                continue;
+            }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            if part_start.elapsed() >= limit {
+                child.kill()?;
+                child.wait()?;
+                break (None, limit);
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        let (parts, records) = if Path::new(&record_path).exists() {
+            let parts = record::read_parts(&record_path);
+            let records = PartRecord::read_all(&record_path).map_err(Error::Record);
+            let _ = fs::remove_file(&record_path);
+            (parts, records?)
+        } else {
+            (None, vec![])
+        };
+
+        let mut results: Vec<PartResult> =
+            records.into_iter().map(PartRecord::into_result).collect();
+
+        // the child announces its parts before running them, so a day that fails during `parse` is reported as such.
+        let parts = parts
+            .or_else(|| registry::get(year, day).map(|s| s.parts.to_vec()))
+            .unwrap_or_else(|| vec![1, 2]);
+        let next_part = parts
+            .iter()
+            .find(|part| !results.iter().any(|r| r.part == **part));
+
+        match (status, next_part) {
+            (None, Some(part)) => {
+                results.push(PartResult::failed(*part, Failure::Timeout(limit), limit));
+            }
+            (Some(status), Some(part)) if !status.success() => {
+                results.push(PartResult::failed(
+                    *part,
+                    Failure::Crash(describe_exit(status)),
+                    part_start.elapsed(),
+                ));
+            }
+            _ => {}
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        Ok(results)
+    }
 
+    /// Run the example tests of the solution bin for a given day, capturing their output.
+    pub fn test_solution(year: Year, day: Day) -> Result<Output, Error> {
+        let output = Command::new("cargo")
+            .args(["test", "--quiet", "--bin", &get_bin_name(year, day)])
+            .args(["--", "--format", "pretty"])
+            .env("RUST_BACKTRACE", "0")
+            .output()?;
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// The path of a bin built by `cargo build`, e.g. `target/release/2023_01`.
+    fn get_bin_path(bin_name: &str, is_release: bool) -> PathBuf {
+        let target_dir = env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+        let profile = if is_release { "release" } else { "debug" };
+        Path::new(&target_dir)
+            .join(profile)
+            .join(format!("{bin_name}{}", env::consts::EXE_SUFFIX))
+    }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    fn count_records(path: &str) -> usize {
+        fs::read_to_string(path).map_or(0, |s| {
+            s.lines().filter(|line| !line.trim().is_empty()).count()
+        })
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    fn describe_exit(status: ExitStatus) -> String {
+        match status.code() {
+            Some(code) => format!("process exited with status {code}"),
+            None => "process was terminated".into(),
+        }
+    }
+}
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+/* -------------------------------------------------------------------------- */
 
-                timings.total_nanos += nanos;
-            });
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, thread, time::Duration};
 
-        timings
+    use super::{run_on_worker, Timeouts, DAY_TIMEOUT_ENV, PART_TIMEOUT_ENV};
+    use crate::template::registry::{Run, Solution};
+    use crate::template::runner::{
+        execute_parse, execute_part, BenchConfig, Failure, PartResult, PARSE,
+    };
+    use crate::{day, year};
+
+    fn sleeping(input: &str, bench: Option<BenchConfig>, on_result: &mut dyn FnMut(PartResult)) {
+        on_result(execute_part(
+            |input: &str| Some(input.len()),
+            input,
+            1,
+            bench,
+        ));
+        on_result(execute_part(
+            |_: &str| {
+                thread::sleep(Duration::from_secs(5));
+                Some(0)
+            },
+            input,
+            2,
+            bench,
+        ));
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn crashing(input: &str, bench: Option<BenchConfig>, on_result: &mut dyn FnMut(PartResult)) {
+        on_result(execute_part(
+            |_: &str| -> Option<u32> { panic!("out of bounds") },
+            input,
+            1,
+            bench,
+        ));
+        on_result(execute_part(
+            |input: &str| Some(input.len()),
+            input,
+            2,
+            bench,
+        ));
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn crashing_parse(
+        input: &str,
+        bench: Option<BenchConfig>,
+        on_result: &mut dyn FnMut(PartResult),
+    ) {
+        let (parsed, result) = execute_parse(
+            |_: &str| -> usize { panic!("unexpected end of input") },
+            input,
+            bench,
+        );
+        on_result(result);
+        let Some(parsed) = parsed else {
+            return;
+        };
+        on_result(execute_part(|n: &usize| Some(*n), &parsed, 1, bench));
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    fn run(parts: &'static [u8], run: Run, timeouts: Timeouts) -> Vec<PartResult> {
+        let solution = Solution::new(year!(2023), day!(1), parts, run);
+        run_on_worker(&solution, "abc".into(), None, timeouts).unwrap()
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn reads_timeouts() {
+        env::set_var(PART_TIMEOUT_ENV, "5");
+        env::set_var(DAY_TIMEOUT_ENV, "-1");
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+        // arguments win over the environment, which wins over the defaults.
+        let timeouts = Timeouts::new(Some(1.5), None);
+        assert_eq!(timeouts.part, Duration::from_millis(1500));
+        assert_eq!(timeouts.day, Timeouts::default().day);
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let timeouts = Timeouts::new(None, Some(10.0));
+        assert_eq!(timeouts.part, Duration::from_secs(5));
+        assert_eq!(timeouts.day, Duration::from_secs(10));
+
+        env::remove_var(PART_TIMEOUT_ENV);
+        env::remove_var(DAY_TIMEOUT_ENV);
+
+        let timeouts = Timeouts::new(None, Some(0.0));
+        assert_eq!(timeouts.part, Timeouts::default().part);
+        assert_eq!(timeouts.day, Timeouts::default().day);
+    }
+
+    #[test]
+    fn times_out_parts() {
+        let timeouts = Timeouts {
+            part: Duration::from_millis(100),
+            day: Duration::from_secs(10),
+        };
+        let results = run(&[1, 2], sleeping, timeouts);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, Some("3".into()));
+        assert_eq!(results[1].part, 2);
+        assert_eq!(
+            results[1].failure,
+            Some(Failure::Timeout(Duration::from_millis(100)))
+        );
+    }
+
+    #[test]
+    fn times_out_days() {
+        let timeouts = Timeouts {
+            part: Duration::from_secs(10),
+            day: Duration::from_millis(200),
+        };
+        let results = run(&[1, 2], sleeping, timeouts);
+
+        assert!(matches!(
+            results[1].failure,
+            Some(Failure::Timeout(limit)) if limit <= Duration::from_millis(200)
+        ));
+    }
+
+    #[test]
+    fn reports_crashes() {
+        let results = run(&[1, 2], crashing, Timeouts::default());
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].failure,
+            Some(Failure::Crash("out of bounds".into()))
+        );
+        assert_eq!(results[1].answer, Some("3".into()));
+    }
+
+    #[test]
+    fn stops_after_crashing_parse() {
+        let results = run(&[PARSE, 1], crashing_parse, Timeouts::default());
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].part, PARSE);
+        assert_eq!(
+            results[0].failure,
+            Some(Failure::Crash("unexpected end of input".into()))
+        );
     }
 }
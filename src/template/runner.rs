@@ -1,74 +1,448 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Once};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::template::answers::Answers;
+use crate::template::input::InputSource;
+use crate::template::ledger::Ledger;
+use crate::template::record::{append_parts, PartRecord, RECORD_FILE_ENV};
+use crate::template::stats::Stats;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// The part number under which the results of a day's `parse` function are reported.
+pub const PARSE: u8 = 0;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// The return types of solution parts.
+/// A part either returns an [`Option`], where [`None`] means that there is no answer,
+/// or a [`Result`], whose error is reported in place of the answer.
+pub trait IntoAnswer {
+    /// Formats the answer, or returns the reason why there is none, if known.
+    fn into_answer(self) -> Result<String, Option<String>>;
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Result<String, Option<String>> {
+        self.map(|answer| answer.to_string()).ok_or(None)
+    }
+}
+
+impl<T: Display, E: Display> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<String, Option<String>> {
+        self.map(|answer| answer.to_string())
+            .map_err(|e| Some(e.to_string()))
+    }
+}
+
+/// Why a part has no answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Failure {
+    /// The part returned an error.
+    Error(String),
+    /// The part panicked, or the process running it exited abnormally.
+    Crash(String),
+    /// The part did not finish within its time limit.
+    Timeout(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Error(e) => write!(f, "{e}"),
+            Failure::Crash(reason) => write!(f, "crashed: {reason}"),
+            Failure::Timeout(limit) => write!(f, "timed out after {limit:.1?}"),
+        }
+    }
+}
+
+impl Failure {
+    /// A short description without details, e.g. `crashed` or `timed out after 60s`.
+    pub fn summary(&self) -> String {
+        match self {
+            Failure::Error(_) => "error".into(),
+            Failure::Crash(_) => "crashed".into(),
+            Failure::Timeout(limit) => format!("timed out after {limit:.0?}"),
+        }
+    }
+}
+
+/// The outcome of running a single solution part, or of parsing the input if `part` is [`PARSE`].
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub failure: Option<Failure>,
+    /// The median duration of all samples.
+    pub duration: Duration,
+    pub stats: Stats,
+    pub sample_durations: Vec<Duration>,
+}
+
+impl PartResult {
+    pub fn new(part: u8, answer: Option<String>, sample_durations: Vec<Duration>) -> Self {
+        let stats = Stats::from_samples(&sample_durations).unwrap_or_default();
+
+        Self {
+            part,
+            answer,
+            failure: None,
+            duration: stats.median,
+            stats,
+            sample_durations,
+        }
+    }
+
+    #[must_use]
+    pub fn with_failure(mut self, failure: Option<Failure>) -> Self {
+        self.failure = failure;
+        self
+    }
+
+    /// Reports and returns the result of a part that was stopped from the outside, e.g. by a timeout.
+    pub fn failed(part: u8, failure: Failure, duration: Duration) -> Self {
+        print!("\r");
+        println!("{}: ✖ {failure}             ", part_label(part));
+        Self::new(part, None, vec![duration]).with_failure(Some(failure))
+    }
+}
+
+/// Configuration of a benchmark run.
+#[derive(Clone, Copy, Debug)]
+pub struct BenchConfig {
+    /// Time spent running the solution before samples are taken.
+    pub warmup: Duration,
+    /// Time spent taking samples. At least [`MIN_SAMPLES`] samples are taken regardless of the budget.
+    pub budget: Duration,
+}
+
+/// Minimum number of samples taken when benchmarking.
+pub const MIN_SAMPLES: usize = 10;
+/// Maximum number of samples taken when benchmarking.
+pub const MAX_SAMPLES: usize = 100_000;
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self::from_budget_millis(1000)
+    }
+}
+
+impl BenchConfig {
+    /// Creates a config with the given time budget. A tenth of the budget is spent warming up.
+    pub fn from_budget_millis(budget: u64) -> Self {
+        let budget = Duration::from_millis(budget);
+        Self {
+            warmup: budget / 10,
+            budget,
+        }
+    }
+
+    /// Reads the `--budget <ms>` argument of the current process, if present.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        args.iter()
+            .position(|x| x == "--budget")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|budget| budget.parse().ok())
+            .map_or_else(Self::default, Self::from_budget_millis)
+    }
+}
+
+/// Returns the benchmark configuration requested by the `--time` argument of the current process.
+fn bench_from_args() -> Option<BenchConfig> {
+    env::args()
+        .any(|x| x == "--time")
+        .then(BenchConfig::from_args)
+}
+
+fn write_record(day: Day, result: &PartResult) {
+    if let Ok(path) = env::var(RECORD_FILE_ENV) {
+        if let Err(e) = PartRecord::from_result(day, result).append_to(&path) {
+            eprintln!("Failed to write record to \"{path}\": {e}");
+        }
+    }
+}
+
+/// Announce the parts a day is about to run, so a parent process can tell which part was running when the day failed.
+pub fn write_parts(day: Day, parts: &[u8]) {
+    if let Ok(path) = env::var(RECORD_FILE_ENV) {
+        if let Err(e) = append_parts(&path, day, parts) {
+            eprintln!("Failed to write record to \"{path}\": {e}");
+        }
+    }
+}
+
+/// Run the `parse` function of a day and return its output, which is shared by both parts.
+/// Returns [`None`] if parsing panicked.
+pub fn run_parse<I: Clone, P>(func: impl Fn(I) -> P, input: I, day: Day) -> Option<P> {
+    let (parsed, result) = execute_parse(func, input, bench_from_args());
+    write_record(day, &result);
+    parsed
+}
+
+pub fn run_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) {
+    let result = execute_part(func, input, part, bench_from_args());
+
+    write_record(day, &result);
+
+    if let Some(answer) = result.answer {
+        submit_result(answer, year, day, part);
+    }
+}
+
+/// Run and print the `parse` function of a day, returning its output and timing.
+/// The output is [`None`] if parsing panicked. The function is benchmarked if a [`BenchConfig`] is passed.
+pub fn execute_parse<I: Clone, P>(
+    func: impl Fn(I) -> P,
+    input: I,
+    bench: Option<BenchConfig>,
+) -> (Option<P>, PartResult) {
+    if is_cancelled() {
+        return (None, PartResult::new(PARSE, None, vec![]));
+    }
+
+    let (parsed, sample_durations) = run_timed(func, input, bench, |parsed| {
+        if !is_cancelled() {
+            print!("Parse");
+        }
+        parsed
+    });
+
+    let result = PartResult::new(PARSE, None, sample_durations);
+
+    match parsed {
+        Ok(parsed) => {
+            if !is_cancelled() {
+                print!("\r");
+                println!("Parse:{}", format_duration(&result.stats));
+            }
+            (Some(parsed), result)
+        }
+        Err(panic) => {
+            print_panic("Parse", &panic);
+            (
+                None,
+                result.with_failure(Some(Failure::Crash(panic.reason))),
+            )
+        }
+    }
+}
+
+/// Run and print a solution part, returning its answer and timing.
+/// A panic of the part is caught and reported like an error.
+/// The part is benchmarked if a [`BenchConfig`] is passed.
+pub fn execute_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+    bench: Option<BenchConfig>,
+) -> PartResult {
+    if is_cancelled() {
+        return PartResult::new(part, None, vec![]);
+    }
+
+    let part_str = part_label(part);
+
+    let (answer, sample_durations) = run_timed(func, input, bench, |result| {
+        let answer = result.into_answer();
+        print_result(&answer, &part_str, "");
+        answer
+    });
+
+    let result = PartResult::new(part, None, sample_durations);
+
+    match answer {
+        Ok(answer) => {
+            print_result(&answer, &part_str, &format_duration(&result.stats));
+            match answer {
+                Ok(answer) => PartResult {
+                    answer: Some(answer),
+                    ..result
+                },
+                Err(error) => {
+                    result.with_failure(error.as_deref().map(|e| Failure::Error(first_line(e))))
+                }
+            }
+        }
+        Err(panic) => {
+            print_panic(&part_str, &panic);
+            result.with_failure(Some(Failure::Crash(panic.reason)))
+        }
+    }
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// A caught panic of a solution part.
+#[derive(Clone, Debug, Default)]
+struct Panic {
+    /// The first line of the panic message.
+    reason: String,
+    /// The rest of the panic message, e.g. the snippet of a [`ParseError`](crate::parse::ParseError).
+    details: String,
+    location: Option<String>,
+}
+
+impl Panic {
+    fn from_hook(info: &PanicHookInfo) -> Self {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".into());
+        // drop the prefix of a failed `unwrap()`, so an unwrapped error reads as the error itself.
+        let message = message
+            .strip_prefix("called `Result::unwrap()` on an `Err` value: ")
+            .unwrap_or(&message);
+        let (reason, details) = message.split_once('\n').unwrap_or((message, ""));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        Self {
+            reason: reason.to_string(),
+            details: details.to_string(),
+            location: info.location().map(ToString::to_string),
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
+thread_local! {
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<Panic>> = const { RefCell::new(None) };
+    static CANCELLED: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// Runs `func`, catching a panic instead of unwinding further.
+/// The default panic message is replaced by the returned [`Panic`], which the caller reports.
+/// Panics outside of `catch_panic`, and on other threads, still use the default hook.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, Panic> {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING.get() {
+                LAST_PANIC.set(Some(Panic::from_hook(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    IS_CATCHING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.set(false);
+
+    result.map_err(|_| LAST_PANIC.take().unwrap_or_default())
+}
+
+/// Lets the parts that run on the current thread be cancelled, e.g. after they ran into a timeout.
+/// Once `flag` is set, the runner stops printing and benchmarking, and skips all remaining parts.
+pub fn cancel_with(flag: Arc<AtomicBool>) {
+    CANCELLED.set(Some(flag));
+}
+
+fn is_cancelled() -> bool {
+    CANCELLED.with_borrow(|flag| flag.as_ref().is_some_and(|f| f.load(Ordering::Relaxed)))
+}
+
+/// Returns the name of a part in output, e.g. `Part 1` or `Parse`.
+pub fn part_label(part: u8) -> String {
+    match part {
+        PARSE => "Parse".into(),
+        part => format!("Part {part}"),
+    }
+}
+
+fn print_panic(part: &str, panic: &Panic) {
+    if is_cancelled() {
+        return;
+    }
+
+    print!("\r");
+    println!("{part}: ✖ {}             ", panic.reason);
+    if !panic.details.is_empty() {
+        eprintln!("{}", panic.details);
+    }
+    if let Some(location) = &panic.location {
+        eprintln!("{ANSI_ITALIC}panicked at {location}{ANSI_RESET}");
+    }
+}
+
+fn first_line(message: &str) -> String {
+    message.lines().next().unwrap_or_default().to_string()
+}
+
+/// Run a solution part. The behavior differs depending on whether a benchmark is requested:
+///  1. without a [`BenchConfig`], the function is executed once.
+///  2. with a [`BenchConfig`], the function is warmed up and then sampled until the time budget is used up.
+///
+/// `on_result` receives the output of the first execution. If that execution panics, the panic is returned
+/// and the function is not benchmarked.
+fn run_timed<I: Clone, T, U>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    bench_config: Option<BenchConfig>,
+    on_result: impl FnOnce(T) -> U,
+) -> (Result<U, Panic>, Vec<Duration>) {
     let timer = Instant::now();
-    let result = {
+    let result = catch_panic(|| {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
         func(input)
-    };
+    });
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let result = match result {
+        Ok(result) => on_result(result),
+        Err(panic) => return (Err(panic), vec![base_time]),
+    };
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let samples = match bench_config {
+        Some(config) => bench(func, input, config),
+        None => vec![base_time],
     };
 
-    (result, run.0, run.1)
+    (Ok(result), samples)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, config: BenchConfig) -> Vec<Duration> {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if !is_cancelled() {
+        print!(" > {ANSI_ITALIC}warming up{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let warmup = Instant::now();
+    while warmup.elapsed() < config.warmup && !is_cancelled() {
+        black_box(func(black_box(input.clone())));
+    }
+
+    if !is_cancelled() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let budget = Instant::now();
 
-    for _ in 0..bench_iterations {
+    while timers.len() < MAX_SAMPLES
+        && (timers.len() < MIN_SAMPLES || budget.elapsed() < config.budget)
+        && !is_cancelled()
+    {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -76,34 +450,31 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    timers
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
-
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(stats: &Stats) -> String {
+    if stats.samples + stats.outliers <= 1 {
+        format!(" ({:.1?})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} ± {:.1?} @ {} samples)",
+            stats.median,
+            stats.stddev,
+            stats.samples + stats.outliers
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Result<String, Option<String>>, part: &str, duration_str: &str) {
+    if is_cancelled() {
+        return;
+    }
+
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -123,7 +494,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Err(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -131,17 +502,33 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(Some(error)) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                // multi-line errors, e.g. a `ParseError`, show their first line as the reason.
+                let (reason, details) = error.split_once('\n').unwrap_or((error, ""));
+                print!("\r");
+                println!("{part}: ✖ {reason}{duration_str}");
+                if !details.is_empty() {
+                    eprintln!("{details}");
+                }
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the solution ran on the puzzle input.
+///  4. the submission ledger does not already know the answer to be wrong.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_cli::Submission, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -164,11 +551,105 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if !InputSource::from_args().is_puzzle() {
+        eprintln!("Refusing to submit: the solution did not run on the puzzle input.");
+        return None;
+    }
+
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("Cannot submit: {e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    // NOTE: storing a ledger that failed to load would erase the submission history.
+    let mut ledger = match Ledger::try_read_from_file(year) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Refusing to submit, as the submission history can not be kept: {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = ledger.check(day, part, &answer) {
+        eprintln!("Refusing to submit `{answer}`: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let submission = aoc_cli::submit(year, day, part, &answer);
+
+    match &submission {
+        Ok(submission) => {
+            ledger.record(day, part, &answer, &submission.outcome);
+            if let Err(e) = ledger.store_file(year) {
+                eprintln!("Failed to store submission ledger: {e}");
+            }
+
+            if submission.outcome == aoc_cli::SubmissionOutcome::Correct {
+                match Answers::try_read_from_file(year) {
+                    Ok(mut answers) => {
+                        answers.set(day, part, &answer);
+                        if let Err(e) = answers.store_file(year) {
+                            eprintln!("Failed to store answers: {e}");
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to store answers: {e}"),
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+        }
+    }
+
+    Some(submission)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{execute_parse, execute_part, Failure, PARSE};
+
+    #[test]
+    fn reports_errors_of_parts() {
+        let result = execute_part(|_: &str| Err::<u32, _>("no path\nmore"), "", 1, None);
+
+        assert_eq!(result.part, 1);
+        assert_eq!(result.answer, None);
+        assert_eq!(result.failure, Some(Failure::Error("no path".into())));
+    }
+
+    #[test]
+    fn runs_parts_after_a_panic() {
+        let result = execute_part(
+            |_: &str| -> Option<u32> { panic!("out of bounds") },
+            "",
+            1,
+            None,
+        );
+        assert_eq!(result.answer, None);
+        assert_eq!(result.failure, Some(Failure::Crash("out of bounds".into())));
+
+        let result = execute_part(|input: &str| Some(input.len()), "abc", 2, None);
+        assert_eq!(result.answer, Some("3".into()));
+        assert_eq!(result.failure, None);
+    }
+
+    #[test]
+    fn reports_panicking_parse() {
+        let (parsed, result) = execute_parse(
+            |_: &str| -> u32 { panic!("unexpected end of input") },
+            "abc",
+            None,
+        );
+
+        assert!(parsed.is_none());
+        assert_eq!(result.part, PARSE);
+        assert_eq!(
+            result.failure,
+            Some(Failure::Crash("unexpected end of input".into()))
+        );
+    }
 }
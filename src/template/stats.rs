@@ -0,0 +1,129 @@
+/// Summary statistics for benchmark samples.
+use std::time::Duration;
+
+/// Tukey's fence factor. Samples further than this many interquartile ranges outside the quartiles are outliers.
+const OUTLIER_FENCE: f64 = 1.5;
+
+/// Statistics of a set of benchmark samples, computed after rejecting outliers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    /// Number of samples the statistics are based on.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Computes statistics from raw samples. Returns `None` if there are no samples.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - OUTLIER_FENCE * iqr, q3 + OUTLIER_FENCE * iqr);
+
+        let retained: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= low && *x <= high)
+            .collect();
+
+        let n = retained.len() as f64;
+        let mean = retained.iter().sum::<f64>() / n;
+        let variance = if retained.len() > 1 {
+            retained.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let nanos = |x: f64| Duration::from_nanos(x.round() as u64);
+
+        Some(Self {
+            mean: nanos(mean),
+            median: nanos(percentile(&retained, 0.5)),
+            p95: nanos(percentile(&retained, 0.95)),
+            min: nanos(retained[0]),
+            max: nanos(retained[retained.len() - 1]),
+            stddev: nanos(variance.sqrt()),
+            samples: retained.len() as u128,
+            outliers: (sorted.len() - retained.len()) as u128,
+        })
+    }
+}
+
+/// Linear interpolation between closest ranks of a sorted, non-empty slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|n| Duration::from_nanos(*n)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50])).unwrap();
+        assert_eq!(stats.mean.as_nanos(), 30);
+        assert_eq!(stats.median.as_nanos(), 30);
+        assert_eq!(stats.p95.as_nanos(), 48);
+        assert_eq!(stats.min.as_nanos(), 10);
+        assert_eq!(stats.max.as_nanos(), 50);
+        assert_eq!(stats.stddev.as_nanos(), 16);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.max.as_nanos(), 102);
+        assert_eq!(stats.median.as_nanos(), 100);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.median.as_nanos(), 42);
+        assert_eq!(stats.stddev.as_nanos(), 0);
+    }
+
+    #[test]
+    fn handles_no_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
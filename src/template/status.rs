@@ -0,0 +1,356 @@
+/// Module that summarises where every day of a year stands: which files exist, whether the tests pass,
+/// which parts are solved and how fast the last benchmark was.
+use std::{collections::HashMap, fmt::Display, fs, path::Path, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::examples::Manifest;
+use crate::template::ledger::Ledger;
+use crate::template::runner::part_label;
+use crate::template::timings::Timings;
+use crate::template::{get_data_path, get_path_for_bin, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_DIM: &str = "\x1b[2m";
+
+/// Whether a part has been solved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Unsolved,
+    /// An answer is recorded in `answers.json`, but advent of code has not accepted a submission.
+    Answered,
+    /// Advent of code accepted a submitted answer.
+    Accepted,
+}
+
+impl PartStatus {
+    pub fn of(day: Day, part: u8, answers: &Answers, ledger: &Ledger) -> Self {
+        if ledger.accepted(day, part).is_some() {
+            PartStatus::Accepted
+        } else if answers.get(day).and_then(|a| a.part(part)).is_some() {
+            PartStatus::Answered
+        } else {
+            PartStatus::Unsolved
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Answered => "answered",
+            PartStatus::Accepted => "accepted",
+        }
+    }
+}
+
+/// The outcome of the last benchmark of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Benchmark {
+    /// The total duration of all parts.
+    Total(Duration),
+    /// The run stopped early, e.g. `Part 2 timed out after 60s`.
+    Failed(String),
+}
+
+impl Display for Benchmark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Benchmark::Total(duration) => write!(f, "{duration:.1?}"),
+            Benchmark::Failed(failure) => write!(f, "{failure}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub is_scaffolded: bool,
+    /// The input exists and is not empty.
+    pub has_input: bool,
+    /// The manifest exists and all of its example files have content.
+    pub has_examples: bool,
+    /// Whether the example tests pass. `None` if they were not run.
+    pub tests: Option<bool>,
+    pub parts: [PartStatus; 2],
+    pub benchmark: Option<Benchmark>,
+}
+
+impl DayStatus {
+    /// Collects the status of a day from its files. The tests are not run.
+    pub fn read(
+        year: Year,
+        day: Day,
+        answers: &Answers,
+        ledger: &Ledger,
+        timings: &Timings,
+    ) -> Self {
+        let has_content = |path: &str| fs::metadata(path).is_ok_and(|m| m.len() > 0);
+
+        let has_examples = Manifest::read_from_file(year, day).is_ok_and(|manifest| {
+            let files = manifest.files();
+            !files.is_empty()
+                && files
+                    .iter()
+                    .all(|file| has_content(&get_data_path(year, &format!("examples/{file}"))))
+        });
+
+        let benchmark =
+            timings
+                .data
+                .iter()
+                .find(|t| t.day == day)
+                .map(|timing| match &timing.failure {
+                    Some(failure) => Benchmark::Failed(format!(
+                        "{} {}",
+                        part_label(failure.part),
+                        failure.failure.summary()
+                    )),
+                    None => Benchmark::Total(Duration::from_nanos(timing.total_nanos as u64)),
+                });
+
+        Self {
+            day,
+            is_scaffolded: Path::new(&get_path_for_bin(year, day)).exists(),
+            has_input: has_content(&get_data_path(year, &format!("inputs/{day}.txt"))),
+            has_examples,
+            tests: None,
+            parts: [1, 2].map(|part| PartStatus::of(day, part, answers, ledger)),
+            benchmark,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A cell of the status table: its text and the colour to print it in.
+struct Cell(String, Option<&'static str>);
+
+impl Cell {
+    fn flag(value: bool) -> Self {
+        if value {
+            Cell("✔".into(), Some(ANSI_GREEN))
+        } else {
+            Cell("✖".into(), Some(ANSI_RED))
+        }
+    }
+
+    fn empty() -> Self {
+        Cell("-".into(), Some(ANSI_DIM))
+    }
+
+    fn part(status: PartStatus) -> Self {
+        match status {
+            PartStatus::Accepted => Cell("★".into(), Some(ANSI_YELLOW)),
+            PartStatus::Answered => Cell("☆".into(), None),
+            PartStatus::Unsolved => Cell::empty(),
+        }
+    }
+}
+
+const HEADER: [&str; 8] = [
+    "Day",
+    "Scaffolded",
+    "Input",
+    "Examples",
+    "Tests",
+    "Part 1",
+    "Part 2",
+    "Benchmark",
+];
+
+/// Renders the status of all days as a table, optionally with ANSI colours.
+pub fn render_table(statuses: &[DayStatus], is_colored: bool) -> String {
+    let rows: Vec<[Cell; 8]> = statuses
+        .iter()
+        .map(|status| {
+            [
+                Cell(status.day.to_string(), None),
+                Cell::flag(status.is_scaffolded),
+                Cell::flag(status.has_input),
+                Cell::flag(status.has_examples),
+                status.tests.map_or_else(Cell::empty, Cell::flag),
+                Cell::part(status.parts[0]),
+                Cell::part(status.parts[1]),
+                status
+                    .benchmark
+                    .as_ref()
+                    .map_or_else(Cell::empty, |benchmark| match benchmark {
+                        Benchmark::Total(_) => Cell(benchmark.to_string(), None),
+                        Benchmark::Failed(_) => Cell(benchmark.to_string(), Some(ANSI_RED)),
+                    }),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = HEADER
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .map(|row| row[i].0.chars().count())
+                .chain([title.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let paint = |text: String, color: Option<&str>| match color {
+        Some(color) if is_colored => format!("{color}{text}{ANSI_RESET}"),
+        _ => text,
+    };
+
+    let header = HEADER
+        .iter()
+        .zip(&widths)
+        .map(|(title, width)| format!("{title:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+
+    let mut lines = vec![paint(header.trim_end().to_string(), Some(ANSI_BOLD))];
+
+    for row in rows {
+        let line = row
+            .into_iter()
+            .zip(&widths)
+            .map(|(Cell(text, color), width)| {
+                let padding = " ".repeat(width - text.chars().count());
+                format!("{}{padding}", paint(text, color))
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.push(String::new());
+    lines.push("★ accepted by advent of code, ☆ answer recorded in answers.json".into());
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "day".into(),
+            JsonValue::Number(value.day.into_inner().into()),
+        );
+        map.insert("scaffolded".into(), JsonValue::Boolean(value.is_scaffolded));
+        map.insert("input".into(), JsonValue::Boolean(value.has_input));
+        map.insert("examples".into(), JsonValue::Boolean(value.has_examples));
+        map.insert(
+            "tests".into(),
+            value.tests.map_or(JsonValue::Null, JsonValue::Boolean),
+        );
+
+        for (part, status) in value.parts.iter().enumerate() {
+            map.insert(
+                format!("part_{}", part + 1),
+                JsonValue::String(status.name().into()),
+            );
+        }
+
+        let (benchmark_nanos, failure) = match &value.benchmark {
+            Some(Benchmark::Total(duration)) => (
+                JsonValue::Number(duration.as_nanos() as f64),
+                JsonValue::Null,
+            ),
+            Some(Benchmark::Failed(failure)) => {
+                (JsonValue::Null, JsonValue::String(failure.clone()))
+            }
+            None => (JsonValue::Null, JsonValue::Null),
+        };
+        map.insert("benchmark_nanos".into(), benchmark_nanos);
+        map.insert("benchmark_failure".into(), failure);
+
+        JsonValue::Object(map)
+    }
+}
+
+/// Serializes the status of all days of a year to JSON.
+pub fn to_json(year: Year, statuses: &[DayStatus]) -> JsonValue {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+    map.insert("year".into(), JsonValue::Number(year.into_inner().into()));
+    map.insert(
+        "days".into(),
+        JsonValue::Array(statuses.iter().map(JsonValue::from).collect()),
+    );
+
+    JsonValue::Object(map)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    use super::{render_table, to_json, Benchmark, DayStatus, PartStatus};
+    use crate::{day, year};
+
+    fn statuses() -> Vec<DayStatus> {
+        vec![
+            DayStatus {
+                day: day!(1),
+                is_scaffolded: true,
+                has_input: true,
+                has_examples: true,
+                tests: Some(true),
+                parts: [PartStatus::Accepted, PartStatus::Answered],
+                benchmark: Some(Benchmark::Total(Duration::from_micros(1500))),
+            },
+            DayStatus {
+                day: day!(2),
+                is_scaffolded: false,
+                has_input: false,
+                has_examples: false,
+                tests: None,
+                parts: [PartStatus::Unsolved, PartStatus::Unsolved],
+                benchmark: Some(Benchmark::Failed("Part 2 timed out after 60s".into())),
+            },
+        ]
+    }
+
+    #[test]
+    fn renders_table() {
+        let table = render_table(&statuses(), false);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "Day  Scaffolded  Input  Examples  Tests  Part 1  Part 2  Benchmark"
+        );
+        assert_eq!(
+            lines[1],
+            "01   ✔           ✔      ✔         ✔      ★       ☆       1.5ms"
+        );
+        assert_eq!(
+            lines[2],
+            "02   ✖           ✖      ✖         -      -       -       Part 2 timed out after 60s"
+        );
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let json = to_json(year!(2023), &statuses());
+
+        let day = &json["days"][0];
+        assert_eq!(day["day"], JsonValue::Number(1.0));
+        assert_eq!(day["tests"], JsonValue::Boolean(true));
+        assert_eq!(day["part_1"], JsonValue::String("accepted".into()));
+        assert_eq!(day["benchmark_nanos"], JsonValue::Number(1_500_000.0));
+
+        let day = &json["days"][1];
+        assert_eq!(day["tests"], JsonValue::Null);
+        assert_eq!(
+            day["benchmark_failure"],
+            JsonValue::String("Part 2 timed out after 60s".into())
+        );
+    }
+}
@@ -1,19 +1,37 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::Path, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::runner::Failure;
+use crate::template::stats::Stats;
+use crate::template::{get_data_path, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+static BASELINE_FILE_NAME: &str = "timings.baseline.json";
 
 /// Represents benchmark times for a single day.
+/// `part_1`, `part_2` and `parse` hold the formatted median duration, the `*_stats` fields the full statistics.
+/// `parse` is only set for days that use a separate `parse` function.
+/// `failure` is set for days whose run stopped early, e.g. because a part timed out or crashed.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse: Option<String>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    pub parse_stats: Option<Stats>,
+    pub failure: Option<DayFailure>,
     pub total_nanos: f64,
 }
 
+/// The first part of a day that failed, and how. Part `0` is the day's `parse` function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayFailure {
+    pub part: u8,
+    pub failure: Failure,
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,16 +40,31 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate the timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        self.store_file_at(&get_data_path(year, TIMINGS_FILE_NAME))
+    }
+
+    /// Dehydrate the timings of a year to the baseline file used by `cargo time --compare`.
+    pub fn store_baseline(&self, year: Year) -> Result<(), Error> {
+        self.store_file_at(&get_data_path(year, BASELINE_FILE_NAME))
+    }
+
+    fn store_file_at(&self, path: &str) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let path = get_data_path(year, TIMINGS_FILE_NAME);
+
+        if !Path::new(&path).exists() {
+            return Timings::default();
+        }
+
+        let s = fs::read_to_string(path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -58,13 +91,14 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        // NOTE: fold from `0.0`, since an empty float sum is `-0.0`.
+        self.data.iter().fold(0.0, |acc, x| acc + x.total_nanos) / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -140,6 +174,36 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            match &value.parse {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "failure".into(),
+            match &value.failure {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+            ("parse_stats", &value.parse_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(stats) => JsonValue::from(stats),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,10 +237,37 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: parse timings and statistics are optional to stay compatible with timings stored by older versions.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+        };
+
+        let parse_stats = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => Stats::try_from(v).map(Some),
+        };
+
+        let failure = match json.get("failure") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(DayFailure::try_from(v)?),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse: parse.cloned(),
+            part_1_stats: parse_stats("part_1_stats")?,
+            part_2_stats: parse_stats("part_2_stats")?,
+            parse_stats: parse_stats("parse_stats")?,
+            failure,
             total_nanos,
         })
     }
@@ -184,6 +275,153 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&DayFailure> for JsonValue {
+    fn from(value: &DayFailure) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("failure".into(), JsonValue::from(&value.failure));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayFailure {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing.failure to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected timing.failure.part to be a number.")?;
+
+        let failure = json
+            .get("failure")
+            .ok_or("Expected timing.failure to have key `failure`.")?;
+
+        Ok(DayFailure {
+            part: part as u8,
+            failure: Failure::try_from(failure)?,
+        })
+    }
+}
+
+/// Failures are stored as an object with a `kind` of `error`, `crash` or `timeout`,
+/// and either the `message` of the failure or the time limit in `limit_nanos`.
+impl From<&Failure> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Failure) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let (kind, key, detail) = match value {
+            Failure::Error(message) => ("error", "message", JsonValue::String(message.clone())),
+            Failure::Crash(message) => ("crash", "message", JsonValue::String(message.clone())),
+            Failure::Timeout(limit) => (
+                "timeout",
+                "limit_nanos",
+                JsonValue::Number(limit.as_nanos() as f64),
+            ),
+        };
+
+        map.insert("kind".into(), JsonValue::String(kind.into()));
+        map.insert(key.into(), detail);
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Failure {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected failure to be a JSON object.")?;
+
+        let message = || {
+            json.get("message")
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or("Expected failure.message to be a string.")
+        };
+
+        match json
+            .get("kind")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+        {
+            Some("error") => Ok(Failure::Error(message()?)),
+            Some("crash") => Ok(Failure::Crash(message()?)),
+            Some("timeout") => json
+                .get("limit_nanos")
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|nanos| Failure::Timeout(Duration::from_nanos(nanos as u64)))
+                .ok_or("Expected failure.limit_nanos to be a number.".into()),
+            _ => Err("Expected failure.kind to be `error`, `crash` or `timeout`.".into()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, duration) in [
+            ("mean_nanos", value.mean),
+            ("median_nanos", value.median),
+            ("p95_nanos", value.p95),
+            ("min_nanos", value.min),
+            ("max_nanos", value.max),
+            ("stddev_nanos", value.stddev),
+        ] {
+            map.insert(key.into(), JsonValue::Number(duration.as_nanos() as f64));
+        }
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let nanos = |key: &str| number(key).map(|n| Duration::from_nanos(n as u64));
+
+        Ok(Stats {
+            mean: nanos("mean_nanos")?,
+            median: nanos("median_nanos")?,
+            p95: nanos("p95_nanos")?,
+            min: nanos("min_nanos")?,
+            max: nanos("max_nanos")?,
+            stddev: nanos("stddev_nanos")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -197,18 +435,33 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -216,6 +469,9 @@ mod tests {
     }
 
     mod deserialization {
+        use std::time::Duration;
+
+        use crate::template::runner::Failure;
         use crate::{day, template::timings::Timings};
 
         #[test]
@@ -230,6 +486,28 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean_nanos": 1100000, "median_nanos": 1000000, "p95_nanos": 1500000, "min_nanos": 900000, "max_nanos": 1600000, "stddev_nanos": 20000, "samples": 90, "outliers": 3 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.median.as_nanos(), 1_000_000);
+            assert_eq!(stats.p95.as_nanos(), 1_500_000);
+            assert_eq!(stats.samples, 90);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_failures() {
+            let json = r#"{ "data": [{ "day": "05", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "failure": { "part": 2, "failure": { "kind": "timeout", "limit_nanos": 60000000000 } } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let failure = timings.data.first().unwrap().failure.as_ref().unwrap();
+            assert_eq!(failure.part, 2);
+            assert_eq!(failure.failure, Failure::Timeout(Duration::from_secs(60)));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -288,11 +566,16 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -302,11 +585,16 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -316,11 +604,16 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -340,6 +633,11 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -360,6 +658,11 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_stats: None,
+                    failure: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -0,0 +1,165 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// Environment variable holding the default year of commands.
+pub static YEAR_ENV: &str = "AOC_YEAR";
+
+/// A valid year of advent of code (i.e. an integer in range 2015 to 9999).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Reads the year from the file name of a solution, e.g. `src/bin/2023_01.rs`.
+    /// Panics if the file name does not follow that format, which fails compilation in a const context.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_bin_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        // NOTE: the file stem starts after the last path separator.
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        assert!(
+            bytes.len() - start == "2023_01.rs".len() && bytes[start + 4] == b'_',
+            "solution files must be named `<year>_<day>.rs`, e.g. `src/bin/2023_01.rs`"
+        );
+
+        let mut year = 0;
+        let mut i = start;
+        while i < start + 4 {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "solution files must be named `<year>_<day>.rs`, e.g. `src/bin/2023_01.rs`"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= FIRST_YEAR, "invalid year in solution file name");
+        Self(year)
+    }
+
+    /// Returns the year set by the `AOC_YEAR` environment variable, if it is valid.
+    pub fn from_env() -> Option<Self> {
+        env::var(YEAR_ENV).ok()?.parse().ok()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent of code, starting with 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value between 2015 and 9999"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!(Year::from_str("2023").unwrap(), 2023);
+        assert!(Year::from_str("2014").is_err());
+        assert!(Year::from_str("23").is_err());
+    }
+
+    #[test]
+    fn reads_year_from_bin_paths() {
+        assert_eq!(Year::__from_bin_path("src/bin/2023_01.rs"), 2023);
+        assert_eq!(Year::__from_bin_path("/root/src/bin/2019_25.rs"), 2019);
+        assert_eq!(Year::__from_bin_path("src\\bin\\2015_12.rs"), 2015);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_bin_paths() {
+        Year::__from_bin_path("src/bin/01.rs");
+    }
+}